@@ -1,8 +1,14 @@
-use std::env;
-use std::path::PathBuf;
-use std::process::Command;
+#[cfg(feature = "c")]
+use std::{env, path::PathBuf, process::Command};
 
 fn main() {
+    #[cfg(feature = "c")]
+    build_c_library();
+}
+
+/// Builds `libbigint.a` with make and generates its bindings.
+#[cfg(feature = "c")]
+fn build_c_library() {
     let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
     let libbigint_path = crate_dir.join("lib/bigint");
     let status = Command::new("make")
@@ -0,0 +1,157 @@
+//! Pluggable allocation of big integer objects.
+//!
+//! Both backends obtain their objects from `newPrimObject`. Without an
+//! installed [`Allocator`] those come from the global allocator and are
+//! never freed. [`with_allocator`] installs an allocator for the duration
+//! of an operation, so a virtual machine can place big integers in its own
+//! heap and reclaim them with its garbage collector.
+
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::sync::MutexGuard;
+
+use crate::{bip, lock_bip, ObjRef, BIP};
+
+/// Provides the objects created by the big integer processor.
+pub trait Allocator {
+    /// Returns a new object with room for `data_size` bytes of data and
+    /// its size stored in the header. It must not return a null pointer.
+    ///
+    /// The allocator may move objects, e.g. by collecting garbage, as long
+    /// as it updates the references held by `registers`, which are the only
+    /// references the library keeps across allocations.
+    fn new_prim_object(
+        &mut self,
+        data_size: usize,
+        registers: &mut BIP,
+    ) -> NonNull<ObjRef>;
+}
+
+/// The allocator installed by `with_allocator`. It is only accessed while
+/// the register lock is held.
+static mut ALLOCATOR: Option<*mut dyn Allocator> = None;
+
+/// Runs `operation` on the big integer processor with `allocator` serving
+/// every `newPrimObject` call.
+///
+/// The register lock is held throughout and the registers are cleared
+/// before and after `operation`, so they only ever reference objects of
+/// `allocator`.
+pub fn with_allocator<T>(
+    allocator: &mut dyn Allocator,
+    operation: impl FnOnce() -> T,
+) -> T {
+    let guard = lock_bip();
+    // The allocator only lives for the duration of this call, during which
+    // `Installed` keeps it registered.
+    let allocator = unsafe {
+        mem::transmute::<*mut dyn Allocator, *mut (dyn Allocator + 'static)>(
+            allocator,
+        )
+    };
+    let _installed = Installed::new(allocator, guard);
+    operation()
+}
+
+/// Allocates an object with the installed allocator, if there is one.
+pub(crate) fn allocate(data_size: usize) -> Option<NonNull<ObjRef>> {
+    unsafe {
+        let allocator = ALLOCATOR?;
+        let registers = &mut *ptr::addr_of_mut!(bip);
+        Some((*allocator).new_prim_object(data_size, registers))
+    }
+}
+
+/// Keeps an allocator installed until dropped, even if the operation
+/// panics.
+struct Installed {
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Installed {
+    fn new(
+        allocator: *mut dyn Allocator,
+        guard: MutexGuard<'static, ()>,
+    ) -> Self {
+        unsafe {
+            clear_registers();
+            ALLOCATOR = Some(allocator);
+        }
+        Installed { _guard: guard }
+    }
+}
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        unsafe {
+            ALLOCATOR = None;
+            clear_registers();
+        }
+    }
+}
+
+unsafe fn clear_registers() {
+    bip.op1 = ptr::null_mut();
+    bip.op2 = ptr::null_mut();
+    bip.res = ptr::null_mut();
+    bip.rem = ptr::null_mut();
+}
+
+/// Allocates from the global allocator and frees every object when
+/// dropped, so results must be copied out before that.
+#[derive(Debug, Default)]
+pub(crate) struct Arena {
+    objects: Vec<NonNull<ObjRef>>,
+}
+
+impl Allocator for Arena {
+    fn new_prim_object(
+        &mut self,
+        data_size: usize,
+        _registers: &mut BIP,
+    ) -> NonNull<ObjRef> {
+        let object = match ObjRef::new(data_size) {
+            Ok(object) => object,
+            Err(err) => crate::fatal_error(err),
+        };
+        self.objects.push(object);
+        object
+    }
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        for object in self.objects.drain(..) {
+            unsafe { ObjRef::free(object) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bigAdd, bigFromInt, getPrimObjectDataPointer};
+
+    #[test]
+    fn test_arena_collects_every_object() {
+        let mut arena = Arena::default();
+        let sum = with_allocator(&mut arena, || unsafe {
+            bigFromInt(40);
+            bip.op1 = bip.res;
+            bigFromInt(2);
+            bip.op2 = bip.res;
+            bigAdd();
+            *getPrimObjectDataPointer(bip.res as *mut ObjRef).add(5)
+        });
+        assert_eq!(sum, 42);
+        assert_eq!(arena.objects.len(), 3);
+    }
+
+    #[test]
+    fn test_registers_are_cleared() {
+        let mut arena = Arena::default();
+        with_allocator(&mut arena, || unsafe { bigFromInt(1) });
+        let _bip = lock_bip();
+        assert!(unsafe { bip.res.is_null() });
+    }
+}
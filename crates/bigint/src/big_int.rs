@@ -0,0 +1,482 @@
+//! Safe wrapper around the big integer processor.
+//!
+//! A [`BigInt`] owns a big integer object with the `Big` layout. Every
+//! operation holds the register lock, loads the operands into `bip`, runs
+//! the library function and copies the result into a new `BigInt`, so the
+//! register protocol never leaks out of this module. The objects created
+//! by the library are allocated in an arena that is freed afterwards.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::os::raw::c_void;
+use std::ptr::{self, NonNull};
+use std::str::FromStr;
+
+use crate::allocator::{with_allocator, Arena};
+use crate::{
+    bigAdd, bigCmp, bigDiv, bigFromInt, bigMul, bigNeg, bigSub, bigToInt, bip,
+    BigObjRef, ObjRef,
+};
+
+/// Size of the digit count and sign that precede the digits of a `Big`.
+pub const BIG_HEADER_SIZE: usize = 5;
+
+const BIG_NEGATIVE: u8 = 0;
+const BIG_POSITIVE: u8 = 1;
+
+/// Largest power of ten that fits into a `u32`, used to convert decimals
+/// nine digits at a time.
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+pub struct BigInt {
+    object: NonNull<ObjRef>,
+}
+
+// A `BigInt` exclusively owns its object and only hands it to the library
+// while the register lock is held.
+unsafe impl Send for BigInt {}
+unsafe impl Sync for BigInt {}
+
+/// The error returned when parsing a `BigInt` fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+/// The error returned when a `BigInt` does not fit into an `i32`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryFromBigIntError;
+
+impl BigInt {
+    /// Copies a big integer from its `Big` representation: the digit count
+    /// in native byte order, the sign and the digits, least significant
+    /// first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is shorter than the digit count demands.
+    pub fn from_data(data: &[u8]) -> BigInt {
+        assert!(data.len() >= BIG_HEADER_SIZE, "big integer without header");
+        let nd = i32::from_ne_bytes([data[0], data[1], data[2], data[3]]);
+        let size = BIG_HEADER_SIZE + nd as usize;
+        assert!(data.len() >= size, "big integer with missing digits");
+        let mut object = match ObjRef::new(size) {
+            Ok(object) => object,
+            Err(err) => crate::fatal_error(err),
+        };
+        unsafe { object.as_mut() }
+            .data_mut()
+            .copy_from_slice(&data[..size]);
+        BigInt { object }
+    }
+
+    /// The `Big` representation of this big integer.
+    pub fn data(&self) -> &[u8] {
+        unsafe { self.object.as_ref() }.data()
+    }
+
+    /// Returns `-1`, `0` or `1` depending on the sign.
+    pub fn signum(&self) -> i32 {
+        if self.digits().is_empty() {
+            0
+        } else if self.is_negative() {
+            -1
+        } else {
+            1
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits().is_empty()
+    }
+
+    /// Returns the quotient and the remainder, or `None` if `divisor` is
+    /// zero. The quotient is truncated towards zero.
+    pub fn checked_div_rem(
+        &self,
+        divisor: &BigInt,
+    ) -> Option<(BigInt, BigInt)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        run(|| unsafe {
+            bip.op1 = self.as_big();
+            bip.op2 = divisor.as_big();
+            bigDiv();
+            Some((BigInt::copy_of(bip.res), BigInt::copy_of(bip.rem)))
+        })
+    }
+
+    fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        match self.checked_div_rem(divisor) {
+            Some(result) => result,
+            None => panic!("attempt to divide by zero"),
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        self.data()[BIG_HEADER_SIZE - 1] == BIG_NEGATIVE
+    }
+
+    fn digits(&self) -> &[u8] {
+        &self.data()[BIG_HEADER_SIZE..]
+    }
+
+    fn from_digits(negative: bool, digits: &[u8]) -> BigInt {
+        let nd = digits
+            .iter()
+            .rposition(|&digit| digit != 0)
+            .map_or(0, |i| i + 1);
+        let sign = if negative && nd > 0 {
+            BIG_NEGATIVE
+        } else {
+            BIG_POSITIVE
+        };
+        let mut data = Vec::with_capacity(BIG_HEADER_SIZE + nd);
+        data.extend_from_slice(&(nd as i32).to_ne_bytes());
+        data.push(sign);
+        data.extend_from_slice(&digits[..nd]);
+        BigInt::from_data(&data)
+    }
+
+    fn as_big(&self) -> BigObjRef {
+        self.object.as_ptr() as *mut c_void
+    }
+
+    /// Copies a result of the library, which may alias an operand.
+    ///
+    /// # Safety
+    ///
+    /// `big` must reference a valid big integer object.
+    unsafe fn copy_of(big: BigObjRef) -> BigInt {
+        let data = crate::getPrimObjectDataPointer(big as *mut ObjRef);
+        let nd = ptr::read_unaligned(data as *const i32);
+        let size = BIG_HEADER_SIZE + nd as usize;
+        BigInt::from_data(std::slice::from_raw_parts(data, size))
+    }
+
+    fn binary(
+        &self,
+        other: &BigInt,
+        operation: unsafe extern "C" fn(),
+    ) -> BigInt {
+        run(|| unsafe {
+            bip.op1 = self.as_big();
+            bip.op2 = other.as_big();
+            operation();
+            BigInt::copy_of(bip.res)
+        })
+    }
+}
+
+/// Runs `operation` on the processor, freeing the objects it creates.
+fn run<T>(operation: impl FnOnce() -> T) -> T {
+    with_allocator(&mut Arena::default(), operation)
+}
+
+impl Drop for BigInt {
+    fn drop(&mut self) {
+        unsafe { ObjRef::free(self.object) };
+    }
+}
+
+impl Clone for BigInt {
+    fn clone(&self) -> Self {
+        BigInt::from_data(self.data())
+    }
+}
+
+impl Default for BigInt {
+    fn default() -> Self {
+        BigInt::from(0)
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(value: i32) -> Self {
+        run(|| unsafe {
+            bigFromInt(value);
+            BigInt::copy_of(bip.res)
+        })
+    }
+}
+
+impl TryFrom<&BigInt> for i32 {
+    type Error = TryFromBigIntError;
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        let digits = value.digits();
+        if digits.len() > 4 || (digits.len() == 4 && digits[3] >= 0x80) {
+            return Err(TryFromBigIntError);
+        }
+        run(|| unsafe {
+            bip.op1 = value.as_big();
+            Ok(bigToInt())
+        })
+    }
+}
+
+impl TryFrom<BigInt> for i32 {
+    type Error = TryFromBigIntError;
+
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        i32::try_from(&value)
+    }
+}
+
+impl PartialEq for BigInt {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for BigInt {}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let cmp = run(|| unsafe {
+            bip.op1 = self.as_big();
+            bip.op2 = other.as_big();
+            bigCmp()
+        });
+        cmp.cmp(&0)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        run(|| unsafe {
+            bip.op1 = self.as_big();
+            bigNeg();
+            BigInt::copy_of(bip.res)
+        })
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -&self
+    }
+}
+
+macro_rules! impl_binary_operator {
+    ($trait:ident, $method:ident, $operation:expr) => {
+        impl $trait<&BigInt> for &BigInt {
+            type Output = BigInt;
+
+            fn $method(self, other: &BigInt) -> BigInt {
+                $operation(self, other)
+            }
+        }
+
+        impl $trait<BigInt> for &BigInt {
+            type Output = BigInt;
+
+            fn $method(self, other: BigInt) -> BigInt {
+                $operation(self, &other)
+            }
+        }
+
+        impl $trait<&BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, other: &BigInt) -> BigInt {
+                $operation(&self, other)
+            }
+        }
+
+        impl $trait<BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, other: BigInt) -> BigInt {
+                $operation(&self, &other)
+            }
+        }
+    };
+}
+
+impl_binary_operator!(Add, add, |a: &BigInt, b| a.binary(b, bigAdd));
+impl_binary_operator!(Sub, sub, |a: &BigInt, b| a.binary(b, bigSub));
+impl_binary_operator!(Mul, mul, |a: &BigInt, b| a.binary(b, bigMul));
+impl_binary_operator!(Div, div, |a: &BigInt, b| a.div_rem(b).0);
+impl_binary_operator!(Rem, rem, |a: &BigInt, b| a.div_rem(b).1);
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut magnitude = self.digits().to_vec();
+        let mut chunks = Vec::new();
+        while !magnitude.is_empty() {
+            let mut remainder = 0;
+            for digit in magnitude.iter_mut().rev() {
+                let value = (remainder << 8) | u64::from(*digit);
+                *digit = (value / u64::from(DECIMAL_CHUNK)) as u8;
+                remainder = value % u64::from(DECIMAL_CHUNK);
+            }
+            while magnitude.last() == Some(&0) {
+                magnitude.pop();
+            }
+            chunks.push(remainder);
+        }
+        let mut decimal = match chunks.pop() {
+            Some(chunk) => chunk.to_string(),
+            None => String::from("0"),
+        };
+        for chunk in chunks.iter().rev() {
+            decimal.push_str(&format!(
+                "{chunk:0width$}",
+                width = DECIMAL_CHUNK_DIGITS
+            ));
+        }
+        f.pad_integral(!self.is_negative(), "", &decimal)
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BigInt({self})")
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, decimal) = match s.as_bytes().split_first() {
+            Some((b'-', decimal)) => (true, decimal),
+            Some((b'+', decimal)) => (false, decimal),
+            _ => (false, s.as_bytes()),
+        };
+        if decimal.is_empty() || !decimal.iter().all(u8::is_ascii_digit) {
+            return Err(ParseBigIntError);
+        }
+        let mut magnitude = Vec::new();
+        for chunk in decimal.chunks(DECIMAL_CHUNK_DIGITS) {
+            let factor = 10u64.pow(chunk.len() as u32);
+            let mut carry = chunk
+                .iter()
+                .fold(0, |value, digit| value * 10 + u64::from(digit - b'0'));
+            for digit in magnitude.iter_mut() {
+                let value = u64::from(*digit) * factor + carry;
+                *digit = value as u8;
+                carry = value >> 8;
+            }
+            while carry > 0 {
+                magnitude.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+        Ok(BigInt::from_digits(negative, &magnitude))
+    }
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid digit found in string")
+    }
+}
+
+impl Error for ParseBigIntError {}
+
+impl fmt::Display for TryFromBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("big integer too big for conversion to int")
+    }
+}
+
+impl Error for TryFromBigIntError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(decimal: &str) -> BigInt {
+        decimal.parse().unwrap()
+    }
+
+    #[test]
+    fn test_from_i32_round_trip() {
+        for value in [0, 1, -1, 255, 256, -65536, i32::MAX, i32::MIN + 1] {
+            let number = BigInt::from(value);
+            assert_eq!(i32::try_from(&number), Ok(value));
+            assert_eq!(number.to_string(), value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_try_into_i32_too_big() {
+        let result: Result<i32, _> = big("2147483648").try_into();
+        assert_eq!(result, Err(TryFromBigIntError));
+        assert_eq!(i32::try_from(big("-2147483648")), Err(TryFromBigIntError));
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for decimal in [
+            "0",
+            "-1",
+            "1000000000",
+            "123456789012345678901234567890",
+            "-123456789012345678901234567890",
+        ] {
+            assert_eq!(big(decimal).to_string(), decimal);
+        }
+        assert_eq!(big("+007").to_string(), "7");
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(format!("{:>5}", big("-42")), "  -42");
+        assert_eq!("".parse::<BigInt>(), Err(ParseBigIntError));
+        assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError));
+        assert_eq!("12a".parse::<BigInt>(), Err(ParseBigIntError));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = big("123456789012345678901234567890");
+        let b = big("-987654321");
+        assert_eq!(&a + &b, big("123456789012345678900246913569"));
+        assert_eq!(&a - &b, big("123456789012345678902222222211"));
+        assert_eq!(&a * &b, big("-121932631124828532112482853211126352690"));
+        assert_eq!(&a / &b, big("-124999998873437499901"));
+        assert_eq!(&a % &b, big("574845669"));
+        assert_eq!(-b, BigInt::from(987654321));
+        assert_eq!(-BigInt::from(0), BigInt::from(0));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut numbers = [
+            big("10"),
+            big("-100000000000"),
+            big("0"),
+            big("99999999999"),
+        ];
+        numbers.sort();
+        let sorted: Vec<String> =
+            numbers.iter().map(BigInt::to_string).collect();
+        assert_eq!(sorted, ["-100000000000", "0", "10", "99999999999"]);
+        assert_eq!(big("5").signum(), 1);
+        assert_eq!(big("-5").signum(), -1);
+        assert_eq!(big("0").signum(), 0);
+    }
+
+    #[test]
+    fn test_checked_div_rem_by_zero() {
+        assert!(big("1").checked_div_rem(&BigInt::default()).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_div_by_zero_panics() {
+        let _ = big("1") / big("0");
+    }
+}
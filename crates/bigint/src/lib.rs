@@ -6,13 +6,38 @@
 #![allow(clippy::unreadable_literal)]
 #![allow(clippy::cognitive_complexity)]
 #![allow(clippy::useless_transmute)]
-include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
-use std::alloc::{alloc, Layout};
+#[cfg(not(any(feature = "native", feature = "c")))]
+compile_error!("either the `native` or the `c` feature must be enabled");
+
+mod allocator;
+mod big_int;
+#[cfg(feature = "c")]
+mod ffi {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+#[cfg(feature = "native")]
+pub mod native;
+#[cfg(all(test, feature = "native"))]
+mod testbip;
+
+pub use allocator::{with_allocator, Allocator};
+pub use big_int::{
+    BigInt, ParseBigIntError, TryFromBigIntError, BIG_HEADER_SIZE,
+};
+#[cfg(feature = "c")]
+pub use ffi::*;
+#[cfg(all(feature = "native", not(feature = "c")))]
+pub use native::*;
+
+use std::alloc::{alloc, dealloc, Layout};
+use std::ffi::CStr;
 use std::os::raw::c_void;
 use std::os::raw::{c_char, c_uchar, c_uint};
 use std::ptr;
 use std::ptr::NonNull;
+use std::slice;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 #[repr(C)]
 pub struct ObjRef {
@@ -21,12 +46,16 @@ pub struct ObjRef {
 }
 
 impl ObjRef {
-    pub fn new(data_size: usize) -> Result<NonNull<ObjRef>, &'static str> {
-        let layout = Layout::from_size_align(
-            std::mem::size_of::<ObjRef>() + data_size - 1,
+    fn layout(data_size: usize) -> Result<Layout, &'static str> {
+        Layout::from_size_align(
+            std::mem::size_of::<ObjRef>() + data_size.max(1) - 1,
             std::mem::align_of::<ObjRef>(),
         )
-        .map_err(|_| "Invalid memory layout")?;
+        .map_err(|_| "Invalid memory layout")
+    }
+
+    pub fn new(data_size: usize) -> Result<NonNull<ObjRef>, &'static str> {
+        let layout = ObjRef::layout(data_size)?;
 
         let ptr = unsafe { alloc(layout) as *mut ObjRef };
 
@@ -41,33 +70,61 @@ impl ObjRef {
         Ok(unsafe { NonNull::new_unchecked(ptr) })
     }
 
+    /// Frees an object allocated by `ObjRef::new`.
+    /// # Safety
+    /// The caller must ensure that `obj` was returned by `ObjRef::new` and
+    /// is not used afterwards.
+    pub unsafe fn free(obj: NonNull<ObjRef>) {
+        let layout = ObjRef::layout(obj.as_ref().size as usize)
+            .expect("layout was valid on allocation");
+        dealloc(obj.as_ptr() as *mut u8, layout);
+    }
+
     pub fn data(&self) -> &[u8] {
-        &self.data
+        unsafe { slice::from_raw_parts(self.data_ptr(), self.size as usize) }
     }
 
     pub fn data_mut(&mut self) -> &mut [u8] {
-        &mut self.data
+        let size = self.size as usize;
+        unsafe { slice::from_raw_parts_mut(self.data_mut_ptr(), size) }
     }
 
     pub fn data_ptr(&self) -> *const u8 {
-        self.data.as_ptr()
+        ptr::addr_of!(self.data) as *const u8
     }
 
     pub fn data_mut_ptr(&mut self) -> *mut u8 {
-        self.data.as_mut_ptr()
+        ptr::addr_of_mut!(self.data) as *mut u8
     }
 }
 
-#[no_mangle]
-pub extern "C" fn fatalError(msg: *const c_char) {
-    let c_str = unsafe { std::ffi::CString::from_raw(msg as *mut c_char) };
-    let message = c_str.to_string_lossy();
+/// The registers of the big integer processor are shared by the whole
+/// process, so each use of them must hold this lock.
+static BIP_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn lock_bip() -> MutexGuard<'static, ()> {
+    BIP_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn fatal_error(message: &str) -> ! {
     eprintln!("Fatal Error: {message}");
     std::process::exit(1);
 }
 
+/// `fatalError` function in Rust
+/// # Safety
+/// The caller must ensure that `msg` points to a nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fatalError(msg: *const c_char) {
+    let c_str = unsafe { CStr::from_ptr(msg) };
+    fatal_error(&c_str.to_string_lossy());
+}
+
 #[no_mangle]
 pub extern "C" fn newPrimObject(data_size: c_uint) -> *mut c_void {
+    if let Some(object) = allocator::allocate(data_size as usize) {
+        return object.as_ptr() as *mut c_void;
+    }
     match ObjRef::new(data_size as usize) {
         Ok(obj_ref) => obj_ref.as_ptr() as *mut c_void,
         Err(err) => {
@@ -90,8 +147,7 @@ pub unsafe extern "C" fn getPrimObjectDataPointer(
         return ptr::null_mut();
     }
 
-    let obj_ref = unsafe { &mut *obj };
-    obj_ref.data_mut_ptr()
+    unsafe { ptr::addr_of_mut!((*obj).data) as *mut c_uchar }
 }
 
 #[cfg(test)]
@@ -100,6 +156,7 @@ mod tests {
 
     #[test]
     fn test_bigint_lib() {
+        let _bip = lock_bip();
         unsafe {
             bigFromInt(2);
             println!("{bip:#?}");
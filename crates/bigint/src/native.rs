@@ -0,0 +1,668 @@
+//! Native implementation of the big integer processor.
+//!
+//! This is a port of `lib/bigint/src/bigint.c`. Numbers have the same
+//! base-256 `Big` layout and live in objects obtained from `newPrimObject`,
+//! so both backends can be used interchangeably.
+//!
+//! # Safety
+//!
+//! All functions operate on the registers in `bip`. The operand registers
+//! must reference valid big integer objects when a function is called.
+#![allow(clippy::missing_safety_doc)]
+
+use std::io::{self, BufRead, Write};
+use std::mem::size_of;
+use std::os::raw::{c_int, c_uchar, c_uint, c_void};
+use std::ptr;
+
+use crate::{fatal_error, getPrimObjectDataPointer, newPrimObject, ObjRef};
+
+/// object representation
+pub type BigObjRef = *mut c_void;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Big {
+    /// number of digits; array may be bigger, nd = 0 exactly when number = 0
+    pub nd: c_int,
+    /// one of BIG_NEGATIVE or BIG_POSITIVE, zero always has BIG_POSITIVE
+    pub sign: c_uchar,
+    /// the digits proper, LS digit first; number base is 256
+    pub digits: [c_uchar; 1],
+}
+
+/// big integer processor registers
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct BIP {
+    /// first (or single) operand
+    pub op1: BigObjRef,
+    /// second operand (if present)
+    pub op2: BigObjRef,
+    /// result of operation
+    pub res: BigObjRef,
+    /// remainder in case of division
+    pub rem: BigObjRef,
+}
+
+/// registers of the processor
+pub static mut bip: BIP = BIP {
+    op1: ptr::null_mut(),
+    op2: ptr::null_mut(),
+    res: ptr::null_mut(),
+    rem: ptr::null_mut(),
+};
+
+const BIG_NEGATIVE: c_uchar = 0;
+const BIG_POSITIVE: c_uchar = 1;
+
+/// Objects may live in a heap that only aligns them to bytes, so the
+/// components of a `Big` are accessed without assuming any alignment.
+unsafe fn big_ptr(big: BigObjRef) -> *mut Big {
+    getPrimObjectDataPointer(big as *mut ObjRef) as *mut Big
+}
+
+unsafe fn get_nd(big: BigObjRef) -> c_int {
+    ptr::read_unaligned(ptr::addr_of!((*big_ptr(big)).nd))
+}
+
+unsafe fn set_nd(big: BigObjRef, nd: c_int) {
+    ptr::write_unaligned(ptr::addr_of_mut!((*big_ptr(big)).nd), nd);
+}
+
+unsafe fn get_sign(big: BigObjRef) -> c_uchar {
+    *ptr::addr_of!((*big_ptr(big)).sign)
+}
+
+unsafe fn set_sign(big: BigObjRef, sign: c_uchar) {
+    *ptr::addr_of_mut!((*big_ptr(big)).sign) = sign;
+}
+
+unsafe fn digit_ptr(big: BigObjRef, i: c_int) -> *mut c_uchar {
+    let digits = ptr::addr_of_mut!((*big_ptr(big)).digits) as *mut c_uchar;
+    digits.add(i as usize)
+}
+
+unsafe fn get_digit(big: BigObjRef, i: c_int) -> c_uchar {
+    *digit_ptr(big, i)
+}
+
+unsafe fn set_digit(big: BigObjRef, i: c_int, digit: c_uchar) {
+    *digit_ptr(big, i) = digit;
+}
+
+/// Sets the number of digits to `nd` minus the leading zero digits.
+unsafe fn set_actual_size(big: BigObjRef, mut nd: c_int) {
+    while nd > 0 && get_digit(big, nd - 1) == 0 {
+        nd -= 1;
+    }
+    set_nd(big, nd);
+}
+
+/// Constructs a new big integer object with room for `nd` digits. No
+/// component of the big integer is set.
+///
+/// All object references stored in places other than the bip registers
+/// may become invalid as soon as this function is called.
+unsafe fn new_big(nd: c_int) -> BigObjRef {
+    let data_size = size_of::<c_int>() as c_int + 1 + nd;
+    newPrimObject(data_size as c_uint)
+}
+
+/// exchange bip.op1 and bip.op2
+unsafe fn big_xchg() {
+    std::mem::swap(&mut bip.op1, &mut bip.op2);
+}
+
+/// big integer unsigned comparison of bip.op1 and bip.op2
+unsafe fn big_ucmp() -> c_int {
+    let mut nd1 = get_nd(bip.op1);
+    let nd2 = get_nd(bip.op2);
+    if nd1 != nd2 {
+        return nd1 - nd2;
+    }
+    while nd1 > 0 {
+        nd1 -= 1;
+        let diff =
+            get_digit(bip.op1, nd1) as c_int - get_digit(bip.op2, nd1) as c_int;
+        if diff != 0 {
+            return diff;
+        }
+    }
+    0
+}
+
+/// big integer unsigned addition, result in bip.res
+unsafe fn big_uadd() {
+    let mut nd1 = get_nd(bip.op1);
+    let mut nd2 = get_nd(bip.op2);
+    let xchg = nd1 < nd2;
+    if xchg {
+        big_xchg();
+        std::mem::swap(&mut nd1, &mut nd2);
+    }
+    bip.res = new_big(nd1 + 1);
+    for i in 0..nd2 {
+        set_digit(bip.res, i, get_digit(bip.op2, i));
+    }
+    for i in nd2..nd1 {
+        set_digit(bip.res, i, 0);
+    }
+    let mut carry: u16 = 0x00;
+    for i in 0..nd1 {
+        let aux =
+            get_digit(bip.op1, i) as u16 + get_digit(bip.res, i) as u16 + carry;
+        set_digit(bip.res, i, (aux & 0xFF) as c_uchar);
+        carry = aux >> 8;
+    }
+    set_digit(bip.res, nd1, carry as c_uchar);
+    set_actual_size(bip.res, nd1 + 1);
+    if xchg {
+        big_xchg();
+    }
+}
+
+/// big integer unsigned subtraction, result in bip.res must not be negative
+unsafe fn big_usub() {
+    let nd1 = get_nd(bip.op1);
+    let nd2 = get_nd(bip.op2);
+    if nd1 < nd2 {
+        fatal_error("internal library error #1 - THIS SHOULD NEVER HAPPEN!");
+    }
+    bip.res = new_big(nd1);
+    for i in 0..nd2 {
+        set_digit(bip.res, i, get_digit(bip.op2, i));
+    }
+    for i in nd2..nd1 {
+        set_digit(bip.res, i, 0);
+    }
+    let mut carry: u16 = 0x01;
+    for i in 0..nd1 {
+        let aux = get_digit(bip.op1, i) as u16 + carry + 0xFF
+            - get_digit(bip.res, i) as u16;
+        set_digit(bip.res, i, (aux & 0xFF) as c_uchar);
+        carry = aux >> 8;
+    }
+    if carry != 0x01 {
+        fatal_error("internal library error #2 - THIS SHOULD NEVER HAPPEN!");
+    }
+    set_actual_size(bip.res, nd1);
+}
+
+/// big integer unsigned multiplication, result in bip.res
+unsafe fn big_umul() {
+    let nd1 = get_nd(bip.op1);
+    let nd2 = get_nd(bip.op2);
+    bip.res = new_big(nd1 + nd2);
+    for i in 0..nd1 {
+        set_digit(bip.res, i, 0);
+    }
+    for j in 0..nd2 {
+        let mut carry: u16 = 0x00;
+        let mut k = j;
+        for i in 0..nd1 {
+            let aux = get_digit(bip.op1, i) as u16
+                * get_digit(bip.op2, j) as u16
+                + get_digit(bip.res, k) as u16
+                + carry;
+            set_digit(bip.res, k, (aux & 0xFF) as c_uchar);
+            carry = aux >> 8;
+            k += 1;
+        }
+        set_digit(bip.res, k, carry as c_uchar);
+    }
+    set_actual_size(bip.res, nd1 + nd2);
+}
+
+/// big integer unsigned division of bip.rem by a single digit divisor,
+/// quotient in bip.rem, remainder is returned
+unsafe fn big_udiv1(divisor: c_uchar) -> c_uchar {
+    let nd = get_nd(bip.rem);
+    let d = divisor as u16;
+    if d == 0 {
+        fatal_error("internal library error #3 - THIS SHOULD NEVER HAPPEN!");
+    }
+    let tmp = new_big(nd);
+    let mut r: u16 = 0;
+    for i in (0..nd).rev() {
+        let aux = (r << 8) | get_digit(bip.rem, i) as u16;
+        set_digit(tmp, i, (aux / d) as c_uchar);
+        r = aux % d;
+    }
+    set_actual_size(tmp, nd);
+    bip.rem = tmp;
+    r as c_uchar
+}
+
+/// big integer unsigned division of bip.op1 by bip.op2,
+/// quotient in bip.res, remainder in bip.rem
+unsafe fn big_udiv() {
+    let nd1 = get_nd(bip.op1);
+    let nd2 = get_nd(bip.op2);
+    if nd2 == 0 {
+        fatal_error("division by zero");
+    }
+    // check for small dividend
+    if big_ucmp() < 0 {
+        bip.res = new_big(0);
+        set_nd(bip.res, 0);
+        bip.rem = new_big(nd1);
+        for i in 0..nd1 {
+            set_digit(bip.rem, i, get_digit(bip.op1, i));
+        }
+        set_nd(bip.rem, nd1);
+        return;
+    }
+    // check for single digit divisor
+    if nd2 == 1 {
+        bip.rem = bip.op1;
+        let r = big_udiv1(get_digit(bip.op2, 0));
+        bip.res = bip.rem;
+        if r == 0 {
+            bip.rem = new_big(0);
+            set_nd(bip.rem, 0);
+        } else {
+            bip.rem = new_big(1);
+            set_nd(bip.rem, 1);
+            set_digit(bip.rem, 0, r);
+        }
+        return;
+    }
+    // determine scale factor for normalization
+    let scale = 256 / (get_digit(bip.op2, nd2 - 1) as u16 + 1);
+    // normalize dividend, result is in bip.rem
+    bip.rem = new_big(nd1 + 1);
+    let mut carry: u16 = 0x00;
+    for i in 0..nd1 {
+        let aux = get_digit(bip.op1, i) as u16 * scale + carry;
+        set_digit(bip.rem, i, (aux & 0xFF) as c_uchar);
+        carry = aux >> 8;
+    }
+    set_digit(bip.rem, nd1, carry as c_uchar);
+    set_nd(bip.rem, nd1 + 1);
+    // normalize divisor, result is in bip.res
+    bip.res = new_big(nd2);
+    carry = 0x00;
+    for i in 0..nd2 {
+        let aux = get_digit(bip.op2, i) as u16 * scale + carry;
+        set_digit(bip.res, i, (aux & 0xFF) as c_uchar);
+        carry = aux >> 8;
+    }
+    if carry != 0x00 {
+        fatal_error("internal library error #4 - THIS SHOULD NEVER HAPPEN!");
+    }
+    set_nd(bip.res, nd2);
+    // allocate quotient
+    let nd3 = nd1 - nd2 + 1;
+    let tmp = new_big(nd3);
+    // extract the two most significand digits of divisor
+    let v1 = get_digit(bip.res, nd2 - 1) as c_int;
+    let v2 = get_digit(bip.res, nd2 - 2) as c_int;
+    // j is index into dividend, k is index into quotient
+    let mut j = nd1;
+    for k in (0..nd3).rev() {
+        // calculate qhat
+        let uj0 = get_digit(bip.rem, j) as c_int;
+        let uj1 = get_digit(bip.rem, j - 1) as c_int;
+        let uj2 = get_digit(bip.rem, j - 2) as c_int;
+        let two = (uj0 << 8) | uj1;
+        let mut qhat = if uj0 == v1 { 255 } else { two / v1 };
+        while qhat * v2 > (((two - qhat * v1) << 8) | uj2) {
+            qhat -= 1;
+        }
+        // multiply and subtract
+        let mut carry: u16 = 0xFF;
+        let mut l = j - nd2;
+        for i in 0..nd2 {
+            let aux = (get_digit(bip.rem, l) as c_int
+                - get_digit(bip.res, i) as c_int * qhat
+                + carry as c_int
+                + 0xFE01) as u16;
+            set_digit(bip.rem, l, (aux & 0xFF) as c_uchar);
+            carry = aux >> 8;
+            l += 1;
+        }
+        let aux = get_digit(bip.rem, l) as u16 + carry + 0xFE01;
+        set_digit(bip.rem, l, (aux & 0xFF) as c_uchar);
+        carry = aux >> 8;
+        // test remainder and possibly add back
+        if carry != 0xFF {
+            qhat -= 1;
+            carry = 0x00;
+            let mut l = j - nd2;
+            for i in 0..nd2 {
+                let aux = get_digit(bip.rem, l) as u16
+                    + get_digit(bip.res, i) as u16
+                    + carry;
+                set_digit(bip.rem, l, (aux & 0xFF) as c_uchar);
+                carry = aux >> 8;
+                l += 1;
+            }
+            let aux = get_digit(bip.rem, l) as u16 + carry;
+            set_digit(bip.rem, l, (aux & 0xFF) as c_uchar);
+            carry = aux >> 8;
+            if carry != 0x01 {
+                fatal_error(
+                    "internal library error #5 - THIS SHOULD NEVER HAPPEN!",
+                );
+            }
+        }
+        // store quotient digit
+        set_digit(tmp, k, qhat as c_uchar);
+        j -= 1;
+    }
+    // finish quotient
+    set_actual_size(tmp, nd3);
+    bip.res = tmp;
+    // finish and unnormalize remainder
+    set_actual_size(bip.rem, nd1 + 1);
+    if big_udiv1(scale as c_uchar) != 0 {
+        fatal_error("internal library error #6 - THIS SHOULD NEVER HAPPEN!");
+    }
+}
+
+fn nil_ref_exception() -> ! {
+    fatal_error("big integer library detected illegal nil reference");
+}
+
+/// big integer sign of bip.op1
+pub unsafe extern "C" fn bigSgn() -> c_int {
+    if bip.op1.is_null() {
+        nil_ref_exception();
+    }
+    if get_nd(bip.op1) == 0 {
+        0
+    } else if get_sign(bip.op1) == BIG_POSITIVE {
+        1
+    } else {
+        -1
+    }
+}
+
+/// big integer comparison of bip.op1 and bip.op2
+pub unsafe extern "C" fn bigCmp() -> c_int {
+    if bip.op1.is_null() || bip.op2.is_null() {
+        nil_ref_exception();
+    }
+    match (get_sign(bip.op1), get_sign(bip.op2)) {
+        (BIG_POSITIVE, BIG_POSITIVE) => big_ucmp(),
+        (BIG_POSITIVE, _) => 1,
+        (_, BIG_POSITIVE) => -1,
+        _ => -big_ucmp(),
+    }
+}
+
+/// big integer negation of bip.op1, result in bip.res
+pub unsafe extern "C" fn bigNeg() {
+    if bip.op1.is_null() {
+        nil_ref_exception();
+    }
+    let nd = get_nd(bip.op1);
+    bip.res = new_big(nd);
+    for i in 0..nd {
+        set_digit(bip.res, i, get_digit(bip.op1, i));
+    }
+    set_nd(bip.res, nd);
+    if get_sign(bip.op1) == BIG_NEGATIVE || nd == 0 {
+        set_sign(bip.res, BIG_POSITIVE);
+    } else {
+        set_sign(bip.res, BIG_NEGATIVE);
+    }
+}
+
+/// big integer addition of bip.op1 and bip.op2, result in bip.res
+pub unsafe extern "C" fn bigAdd() {
+    if bip.op1.is_null() || bip.op2.is_null() {
+        nil_ref_exception();
+    }
+    match (get_sign(bip.op1), get_sign(bip.op2)) {
+        (BIG_POSITIVE, BIG_POSITIVE) => {
+            big_uadd();
+            set_sign(bip.res, BIG_POSITIVE);
+        }
+        (BIG_POSITIVE, _) => {
+            if big_ucmp() >= 0 {
+                big_usub();
+                set_sign(bip.res, BIG_POSITIVE);
+            } else {
+                big_xchg();
+                big_usub();
+                set_sign(bip.res, BIG_NEGATIVE);
+                big_xchg();
+            }
+        }
+        (_, BIG_POSITIVE) => {
+            if big_ucmp() <= 0 {
+                big_xchg();
+                big_usub();
+                set_sign(bip.res, BIG_POSITIVE);
+                big_xchg();
+            } else {
+                big_usub();
+                set_sign(bip.res, BIG_NEGATIVE);
+            }
+        }
+        _ => {
+            big_uadd();
+            set_sign(bip.res, BIG_NEGATIVE);
+        }
+    }
+}
+
+/// big integer subtraction of bip.op2 from bip.op1, result in bip.res
+pub unsafe extern "C" fn bigSub() {
+    if bip.op1.is_null() || bip.op2.is_null() {
+        nil_ref_exception();
+    }
+    match (get_sign(bip.op1), get_sign(bip.op2)) {
+        (BIG_POSITIVE, BIG_POSITIVE) => {
+            if big_ucmp() >= 0 {
+                big_usub();
+                set_sign(bip.res, BIG_POSITIVE);
+            } else {
+                big_xchg();
+                big_usub();
+                set_sign(bip.res, BIG_NEGATIVE);
+                big_xchg();
+            }
+        }
+        (BIG_POSITIVE, _) => {
+            big_uadd();
+            set_sign(bip.res, BIG_POSITIVE);
+        }
+        (_, BIG_POSITIVE) => {
+            big_uadd();
+            set_sign(bip.res, BIG_NEGATIVE);
+        }
+        _ => {
+            if big_ucmp() <= 0 {
+                big_xchg();
+                big_usub();
+                set_sign(bip.res, BIG_POSITIVE);
+                big_xchg();
+            } else {
+                big_usub();
+                set_sign(bip.res, BIG_NEGATIVE);
+            }
+        }
+    }
+}
+
+/// big integer multiplication of bip.op1 and bip.op2, result in bip.res
+pub unsafe extern "C" fn bigMul() {
+    if bip.op1.is_null() || bip.op2.is_null() {
+        nil_ref_exception();
+    }
+    big_umul();
+    if get_sign(bip.op1) == get_sign(bip.op2) || get_nd(bip.res) == 0 {
+        set_sign(bip.res, BIG_POSITIVE);
+    } else {
+        set_sign(bip.res, BIG_NEGATIVE);
+    }
+}
+
+/// big integer division of bip.op1 by bip.op2, truncating towards zero,
+/// quotient in bip.res, remainder in bip.rem
+pub unsafe extern "C" fn bigDiv() {
+    if bip.op1.is_null() || bip.op2.is_null() {
+        nil_ref_exception();
+    }
+    big_udiv();
+    if get_sign(bip.op1) == get_sign(bip.op2) || get_nd(bip.res) == 0 {
+        set_sign(bip.res, BIG_POSITIVE);
+    } else {
+        set_sign(bip.res, BIG_NEGATIVE);
+    }
+    if get_sign(bip.op1) == BIG_POSITIVE || get_nd(bip.rem) == 0 {
+        set_sign(bip.rem, BIG_POSITIVE);
+    } else {
+        set_sign(bip.rem, BIG_NEGATIVE);
+    }
+}
+
+/// conversion int --> big, result in bip.res
+pub unsafe extern "C" fn bigFromInt(n: c_int) {
+    let nd = size_of::<c_int>() as c_int;
+    bip.res = new_big(nd);
+    if n < 0 {
+        set_sign(bip.res, BIG_NEGATIVE);
+    } else {
+        set_sign(bip.res, BIG_POSITIVE);
+    }
+    let magnitude = n.unsigned_abs();
+    for i in 0..nd {
+        set_digit(bip.res, i, (magnitude >> (8 * i)) as c_uchar);
+    }
+    set_actual_size(bip.res, nd);
+}
+
+/// conversion big --> int of bip.op1
+pub unsafe extern "C" fn bigToInt() -> c_int {
+    if bip.op1.is_null() {
+        nil_ref_exception();
+    }
+    let nd = get_nd(bip.op1);
+    if nd > 4 || (nd == 4 && get_digit(bip.op1, 3) >= 0x80) {
+        fatal_error("big integer too big for conversion to int");
+    }
+    let mut res: c_int = 0;
+    for i in (0..nd).rev() {
+        res <<= 8;
+        res |= get_digit(bip.op1, i) as c_int;
+    }
+    if get_sign(bip.op1) == BIG_NEGATIVE {
+        res = -res;
+    }
+    res
+}
+
+fn peek<R: BufRead>(input: &mut R) -> io::Result<Option<u8>> {
+    Ok(input.fill_buf()?.first().copied())
+}
+
+/// read a big integer, result in bip.res
+pub unsafe fn bigRead<R: BufRead>(input: &mut R) -> io::Result<()> {
+    let mut c = peek(input)?;
+    while matches!(c, Some(b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r')) {
+        input.consume(1);
+        c = peek(input)?;
+    }
+    let positive = c != Some(b'-');
+    if matches!(c, Some(b'-' | b'+')) {
+        input.consume(1);
+        c = peek(input)?;
+    }
+    if !c.is_some_and(|c| c.is_ascii_digit()) {
+        fatal_error("no digits in input");
+    }
+    bigFromInt(10);
+    bip.rem = bip.res;
+    bigFromInt(0);
+    while let Some(digit) = c.filter(u8::is_ascii_digit) {
+        bip.op1 = bip.res;
+        bip.op2 = bip.rem;
+        big_umul();
+        bip.op1 = bip.res;
+        bigFromInt((digit - b'0') as c_int);
+        bip.op2 = bip.res;
+        big_uadd();
+        input.consume(1);
+        c = peek(input)?;
+    }
+    if positive || get_nd(bip.res) == 0 {
+        set_sign(bip.res, BIG_POSITIVE);
+    } else {
+        set_sign(bip.res, BIG_NEGATIVE);
+    }
+    Ok(())
+}
+
+/// print the big integer in bip.op1
+pub unsafe fn bigPrint<W: Write>(out: &mut W) -> io::Result<()> {
+    if bip.op1.is_null() {
+        nil_ref_exception();
+    }
+    let mut nd = get_nd(bip.op1);
+    if nd == 0 {
+        return write!(out, "0");
+    }
+    if get_sign(bip.op1) == BIG_NEGATIVE {
+        write!(out, "-")?;
+    }
+    // number of digits in base 10 = number of digits
+    // in base 256 * log10(256), and log10(256) < 2.5
+    nd = 2 * nd + nd / 2;
+    bip.rem = bip.op1;
+    bigFromInt(10);
+    bip.op2 = bip.res;
+    bigFromInt(1);
+    while nd != 0 {
+        bip.op1 = bip.res;
+        big_umul();
+        nd -= 1;
+    }
+    bip.op1 = bip.rem;
+    bip.op2 = bip.res;
+    let mut skip_zero = true;
+    loop {
+        big_udiv();
+        if get_nd(bip.res) == 0 {
+            if !skip_zero {
+                write!(out, "0")?;
+            }
+        } else {
+            if get_nd(bip.res) != 1 {
+                fatal_error(
+                    "internal library error #7 - THIS SHOULD NEVER HAPPEN!",
+                );
+            }
+            write!(out, "{}", (get_digit(bip.res, 0) + b'0') as char)?;
+            skip_zero = false;
+        }
+        bip.op1 = bip.rem;
+        bip.rem = bip.op2;
+        let r = big_udiv1(10);
+        bip.op2 = bip.rem;
+        if r != 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// dump a big integer object
+pub unsafe fn bigDump<W: Write>(out: &mut W, big: BigObjRef) -> io::Result<()> {
+    if big.is_null() {
+        nil_ref_exception();
+    }
+    let nd = get_nd(big);
+    let sign = if get_sign(big) == BIG_POSITIVE {
+        '+'
+    } else {
+        '-'
+    };
+    write!(out, "[{nd} {sign}")?;
+    for i in 0..nd {
+        write!(out, " {:02X}", get_digit(big, i))?;
+    }
+    write!(out, "]")
+}
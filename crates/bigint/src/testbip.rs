@@ -0,0 +1,433 @@
+//! Port of `lib/bigint/tst/testbip.c` for the native backend.
+//!
+//! `assets/testbip.out` holds the output of the C `testbip` for every test
+//! except test 11, which ends the process with a division by zero error.
+
+use std::io::Write;
+use std::os::raw::c_int;
+
+use crate::native::*;
+use crate::{getPrimObjectDataPointer, ObjRef};
+
+type Out = Vec<u8>;
+
+unsafe fn dump(out: &mut Out, prefix: &str, x: BigObjRef, suffix: &str) {
+    write!(out, "{prefix}").unwrap();
+    bigDump(out, x).unwrap();
+    write!(out, "{suffix}").unwrap();
+}
+
+unsafe fn set_digits(x: BigObjRef, digits: &[u8]) {
+    let data = getPrimObjectDataPointer(x as *mut ObjRef);
+    for (i, digit) in digits.iter().enumerate() {
+        *data.add(5 + i) = *digit;
+    }
+}
+
+const REPRESENTATION: [(c_int, &str); 20] = [
+    (0, "+0x00000000"),
+    (1, "+0x00000001"),
+    (2, "+0x00000002"),
+    (0x12, "+0x00000012"),
+    (0x123, "+0x00000123"),
+    (0x1234, "+0x00001234"),
+    (0x12345, "+0x00012345"),
+    (0x123456, "+0x00123456"),
+    (0x1234567, "+0x01234567"),
+    (0x12345678, "+0x12345678"),
+    (-0, "-0x00000000"),
+    (-1, "-0x00000001"),
+    (-2, "-0x00000002"),
+    (-0x12, "-0x00000012"),
+    (-0x123, "-0x00000123"),
+    (-0x1234, "-0x00001234"),
+    (-0x12345, "-0x00012345"),
+    (-0x123456, "-0x00123456"),
+    (-0x1234567, "-0x01234567"),
+    (-0x12345678, "-0x12345678"),
+];
+
+unsafe fn test00(out: &mut Out) {
+    for (n, text) in REPRESENTATION {
+        bigFromInt(n);
+        dump(out, &format!("{text} = "), bip.res, "\n");
+    }
+}
+
+unsafe fn test01(out: &mut Out) {
+    for (n, text) in REPRESENTATION {
+        bigFromInt(n);
+        bip.op1 = bip.res;
+        writeln!(out, "sign({text}) = {}", bigSgn()).unwrap();
+    }
+}
+
+unsafe fn test02(out: &mut Out) {
+    let m = [0, 100, -100, 101, -101, 12345678, -12345678];
+    let mut n = [std::ptr::null_mut(); 7];
+    for i in 0..7 {
+        bigFromInt(m[i]);
+        n[i] = bip.res;
+        writeln!(out, "n[{i}] = {}", m[i]).unwrap();
+    }
+    for i in 0..7 {
+        for j in 0..7 {
+            write!(out, "{:12} ", m[i]).unwrap();
+            bip.op1 = n[i];
+            bip.op2 = n[j];
+            let res = bigCmp();
+            let relation = match res {
+                res if res < 0 => "<",
+                res if res > 0 => ">",
+                _ => "=",
+            };
+            writeln!(out, "{relation} {:12}", m[j]).unwrap();
+        }
+    }
+}
+
+unsafe fn test03(out: &mut Out) {
+    for n in [0, 1, -1, 0x12345678, -0x12345678] {
+        bigFromInt(n);
+        bip.op1 = bip.res;
+        bigNeg();
+        dump(out, "+n = ", bip.op1, "\n");
+        dump(out, "-n = ", bip.res, "\n");
+    }
+}
+
+unsafe fn table(
+    out: &mut Out,
+    m: &[c_int],
+    operation: unsafe extern "C" fn(),
+    symbol: char,
+) {
+    let mut n = Vec::new();
+    for m in m {
+        bigFromInt(*m);
+        n.push(bip.res);
+    }
+    for i in 0..m.len() {
+        for j in 0..m.len() {
+            bip.op1 = n[i];
+            bip.op2 = n[j];
+            operation();
+            write!(out, "{:12} {symbol} {:12} = ", m[i], m[j]).unwrap();
+            dump(out, "", bip.res, "\n");
+        }
+    }
+}
+
+const EQUAL_SIZES: [c_int; 6] = [
+    0x12345679,
+    0x12345678,
+    0x12345677,
+    -0x12345677,
+    -0x12345678,
+    -0x12345679,
+];
+
+const DIFFERENT_SIZES: [c_int; 5] = [0x12345678, 1, 0, -1, -0x12345678];
+
+unsafe fn test04(out: &mut Out) {
+    table(out, &EQUAL_SIZES, bigAdd, '+');
+}
+
+unsafe fn test05(out: &mut Out) {
+    table(out, &DIFFERENT_SIZES, bigAdd, '+');
+}
+
+unsafe fn test06(out: &mut Out) {
+    table(out, &EQUAL_SIZES, bigSub, '-');
+}
+
+unsafe fn test07(out: &mut Out) {
+    table(out, &DIFFERENT_SIZES, bigSub, '-');
+}
+
+unsafe fn test08(out: &mut Out) {
+    bigFromInt(1);
+    for i in 1..100 {
+        bip.op1 = bip.res;
+        bip.op2 = bip.res;
+        bigAdd();
+        write!(out, "2 ^ {i:2} = ").unwrap();
+        dump(out, "", bip.res, "\n");
+    }
+}
+
+unsafe fn test09(out: &mut Out) {
+    bigFromInt(2);
+    bip.op2 = bip.res;
+    for i in 1..10 {
+        bip.res = bip.op2;
+        for _ in 1..i * i {
+            bip.op1 = bip.res;
+            bigMul();
+        }
+        write!(out, "2 ^ ({i} ^ 2) = ").unwrap();
+        dump(out, "", bip.res, "\n");
+    }
+}
+
+unsafe fn factorial(mut n: c_int) {
+    bigFromInt(1);
+    while n > 0 {
+        bip.op1 = bip.res;
+        bigFromInt(n);
+        bip.op2 = bip.res;
+        bigMul();
+        n -= 1;
+    }
+}
+
+unsafe fn test10(out: &mut Out) {
+    for i in 0..100 {
+        factorial(i);
+        write!(out, "{i:2}! = ").unwrap();
+        dump(out, "", bip.res, "\n");
+    }
+}
+
+unsafe fn divide(out: &mut Out, n: c_int, m: c_int) {
+    bigFromInt(n);
+    bip.op1 = bip.res;
+    bigFromInt(m);
+    bip.op2 = bip.res;
+    bigDiv();
+    write!(out, "{n:12} / {m:12} = ").unwrap();
+    dump(out, "", bip.res, "\n");
+    write!(out, "{n:12} % {m:12} = ").unwrap();
+    dump(out, "", bip.rem, "\n");
+}
+
+unsafe fn test12(out: &mut Out) {
+    for (n, m) in [
+        (12, 12345678),
+        (12345677, 12345678),
+        (-12, 12345678),
+        (-12345677, 12345678),
+        (12, -12345678),
+        (12345677, -12345678),
+        (-12, -12345678),
+        (-12345677, -12345678),
+    ] {
+        divide(out, n, m);
+    }
+}
+
+unsafe fn test13(out: &mut Out) {
+    for n in [12345678, -12345678] {
+        for m in [1, 17, 255] {
+            divide(out, n, m);
+        }
+    }
+    for n in [12345678, -12345678] {
+        for m in [-1, -17, -255] {
+            divide(out, n, m);
+        }
+    }
+}
+
+/// The C version tries every dividend below 2^18, which takes too long
+/// without freeing the intermediate objects. A result that deviates is
+/// still reported as '?'.
+unsafe fn test14(out: &mut Out) {
+    write!(out, "divisor").unwrap();
+    for m in 0..256 {
+        if m % 8 == 0 {
+            write!(out, "\n{m:3} to {:3}:   ", m + 7).unwrap();
+        }
+        if m == 0 {
+            write!(out, " ").unwrap();
+            continue;
+        }
+        bigFromInt(m);
+        let divisor = bip.res;
+        let mut ok = true;
+        for n in 0..(1 << 8) {
+            bigFromInt(n);
+            let dividend = bip.res;
+            bip.op1 = dividend;
+            bip.op2 = divisor;
+            bigDiv();
+            bip.op1 = bip.res;
+            bigMul();
+            bip.op1 = bip.res;
+            bip.op2 = bip.rem;
+            bigAdd();
+            bip.op1 = bip.res;
+            bip.op2 = dividend;
+            if bigCmp() != 0 {
+                ok = false;
+            }
+        }
+        write!(out, "{}", if ok { '.' } else { '?' }).unwrap();
+    }
+    writeln!(out).unwrap();
+}
+
+unsafe fn test15(out: &mut Out) {
+    let scales = [0xFF, 0x80, 0x7F, 0x55, 0x2F, 0x18, 0x0A, 0x03, 0x02, 0x01];
+    for (i, scale) in scales.into_iter().enumerate() {
+        if i != 0 {
+            writeln!(out, "{}", "-".repeat(50)).unwrap();
+        }
+        divide(out, 123456789, (scale << 16) + 0x1234);
+    }
+}
+
+unsafe fn verify_division(out: &mut Out) {
+    dump(out, "", bip.op1, " / ");
+    dump(out, "", bip.op2, " =\n");
+    bigDiv();
+    dump(out, "", bip.res, " R. ");
+    dump(out, "", bip.rem, "\n");
+    bip.op1 = bip.res;
+    bigMul();
+    bip.op1 = bip.res;
+    bip.op2 = bip.rem;
+    bigAdd();
+    dump(out, "quotient * divisor + remainder =\n", bip.res, "\n");
+}
+
+unsafe fn test16(out: &mut Out) {
+    bigFromInt(0x12345678);
+    bip.op1 = bip.res;
+    bigFromInt(0x66554433);
+    bip.op2 = bip.res;
+    bigMul();
+    bip.op1 = bip.res;
+    bigFromInt(12);
+    bip.op2 = bip.res;
+    bigMul();
+    bip.op1 = bip.res;
+    bigFromInt(0x5764);
+    bip.op2 = bip.res;
+    verify_division(out);
+}
+
+/// Prepares two big integers with 8 and 4 digits, respectively.
+unsafe fn test_digits(out: &mut Out, dividend: [u8; 8], divisor: [u8; 4]) {
+    bigFromInt(0x11111111);
+    bip.op1 = bip.res;
+    bip.op2 = bip.res;
+    bigMul();
+    bip.op1 = bip.res;
+    set_digits(bip.op1, &dividend);
+    set_digits(bip.op2, &divisor);
+    verify_division(out);
+}
+
+unsafe fn test17(out: &mut Out) {
+    test_digits(
+        out,
+        [0xF2, 0xFB, 0xE3, 0x46, 0x7C, 0xC2, 0x54, 0xF8],
+        [0x1B, 0xE8, 0xE7, 0x8D],
+    );
+}
+
+unsafe fn test18(out: &mut Out) {
+    test_digits(
+        out,
+        [0x4D, 0xCC, 0x8C, 0x18, 0x34, 0xDF, 0x1D, 0xFD],
+        [0x69, 0xF4, 0x94, 0x37],
+    );
+}
+
+unsafe fn test19(out: &mut Out) {
+    for n in [0x7FFFFFFF, 0x00000001, 0x00000000, -0x00000001, -0x7FFFFFFF] {
+        bigFromInt(n);
+        bip.op1 = bip.res;
+        let m = bigToInt();
+        writeln!(out, "{n:12} = {m:12}").unwrap();
+    }
+}
+
+unsafe fn test20(out: &mut Out) {
+    write!(out, "please enter a number: ").unwrap();
+    bigRead(&mut &b" -123456789012345678901234567890\n"[..]).unwrap();
+    dump(out, "", bip.res, "\n");
+}
+
+unsafe fn test21(out: &mut Out) {
+    for n in [
+        0,
+        1,
+        -1,
+        0x1234,
+        -0x1234,
+        0x186A0,
+        -0x186A0,
+        0x12345678,
+        -0x12345678,
+    ] {
+        bigFromInt(n);
+        dump(out, "", bip.res, " = ");
+        bip.op1 = bip.res;
+        bigPrint(out).unwrap();
+        writeln!(out).unwrap();
+    }
+    bigFromInt(987654321);
+    bip.op1 = bip.res;
+    bip.op2 = bip.res;
+    bigMul();
+    bip.op1 = bip.res;
+    bip.op2 = bip.res;
+    bigMul();
+    dump(out, "", bip.res, " =\n");
+    bip.op1 = bip.res;
+    bigPrint(out).unwrap();
+    writeln!(out).unwrap();
+}
+
+type TestFunc = unsafe fn(&mut Out);
+
+const TESTS: [(usize, TestFunc, &str); 21] = [
+    (0, test00, "representation"),
+    (1, test01, "sign"),
+    (2, test02, "comparison"),
+    (3, test03, "negation"),
+    (4, test04, "addition, equal sizes"),
+    (5, test05, "addition, different sizes"),
+    (6, test06, "subtraction, equal sizes"),
+    (7, test07, "subtraction, different sizes"),
+    (8, test08, "2^n by addition"),
+    (9, test09, "2^(n^2) by multiplication"),
+    (10, test10, "factorial"),
+    (12, test12, "division, small dividend"),
+    (13, test13, "division, single digit divisor"),
+    (14, test14, "division, systematic test of simple cases"),
+    (
+        15,
+        test15,
+        "division, general case, different scale factors",
+    ),
+    (16, test16, "division, general case, qhat (first guess)"),
+    (
+        17,
+        test17,
+        "division, general case, qhat (decremented twice)",
+    ),
+    (
+        18,
+        test18,
+        "division, general case, qhat (final correction)",
+    ),
+    (19, test19, "conversion big --> int"),
+    (20, test20, "read from file"),
+    (21, test21, "print to file"),
+];
+
+#[test]
+fn test_native_backend_matches_testbip() {
+    let _bip = crate::lock_bip();
+    let mut out = Vec::new();
+    for (number, test, explanation) in TESTS {
+        writeln!(out, "Test {number}: {explanation}").unwrap();
+        unsafe { test(&mut out) };
+    }
+    let output = String::from_utf8(out).unwrap();
+    let expected = std::fs::read_to_string("assets/testbip.out").unwrap();
+    assert_eq!(output, expected);
+}
@@ -1,13 +1,347 @@
-use clap::{command, Parser};
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{command, Parser, Subcommand};
+
+/// The largest heap in KiB. Objects are addressed by their offset into the
+/// heap, and forwarding pointers only hold 30 bits of it.
+pub const MAX_HEAP_MEMORY: usize = 1 << 20;
 
 /// Command-line arguments for the NinjaVM
-#[derive(Parser, Debug)]
-#[command(name = "njvm", version, about)]
-struct Args {
+///
+/// `--help` and `--version` are plain flags, so the virtual machine prints
+/// the same texts as the reference implementation.
+#[derive(Parser, Debug, Default, Eq, PartialEq, Clone)]
+#[command(
+    name = "njvm",
+    disable_help_flag = true,
+    disable_version_flag = true,
+    args_override_self = true
+)]
+pub struct Args {
+    /// Sets the stack size to n KiB
+    #[arg(long, value_name = "n", value_parser = size, allow_negative_numbers = true)]
+    pub stack: Option<usize>,
+
+    /// Sets the heap size to n KiB
+    #[arg(long, value_name = "n", value_parser = heap_size, allow_negative_numbers = true)]
+    pub heap: Option<usize>,
+
+    /// Shows garbage collection statistics
+    #[arg(long)]
+    pub gcstats: bool,
+
+    /// Purges old objects after collection
+    #[arg(long)]
+    pub gcpurge: bool,
+
     /// Enables debug mode
     #[arg(long)]
-    debug: bool,
+    pub debug: bool,
+
+    /// Runs binaries with the instructions of version n
+    #[arg(long, value_name = "n", allow_negative_numbers = true)]
+    pub profile: Option<u32>,
+
+    /// Stops the program after n instructions
+    #[arg(
+        long,
+        value_name = "n",
+        value_parser = steps,
+        allow_negative_numbers = true,
+        conflicts_with = "debug"
+    )]
+    pub max_steps: Option<u64>,
+
+    /// Verifies the code file before running it
+    #[arg(long)]
+    pub verify: bool,
+
+    /// Names addresses after the labels of a map file
+    #[arg(long, value_name = "file")]
+    pub map: Option<String>,
+
+    /// Shows the version
+    #[arg(long)]
+    pub version: bool,
+
+    /// Shows the help
+    #[arg(long)]
+    pub help: bool,
+
+    /// The file to execute, `-` for stdin
+    pub file: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Tasks that work on a code file without running it.
+#[derive(Subcommand, Debug, Eq, PartialEq, Clone)]
+pub enum Command {
+    /// Verifies the code file and exits
+    Verify {
+        /// The file to verify, `-` for stdin
+        file: String,
+    },
+    /// Assembles a Ninja assembler file into a code file
+    Asm {
+        /// The assembler file, `-` for stdin
+        input: String,
+
+        /// The code file to write
+        #[arg(short, long, value_name = "file")]
+        output: String,
+
+        /// Writes the address of every label to a map file
+        #[arg(long, value_name = "file")]
+        map: Option<String>,
+
+        /// Appends labels, variable names and source lines to the code file
+        #[arg(short = 'g', long)]
+        debug_info: bool,
+    },
+    /// Disassembles a code file into a Ninja assembler file
+    Disasm {
+        /// The code file, `-` for stdin
+        input: String,
+
+        /// The assembler file to write instead of stdout
+        #[arg(short, long, value_name = "file")]
+        output: Option<String>,
+
+        /// Names labels after a map file written by `asm --map`
+        #[arg(long, value_name = "file")]
+        map: Option<String>,
+    },
+}
+
+impl Args {
+    /// Parses the arguments following the program name.
+    pub fn try_parse_args<I, T>(args: I) -> Result<Self, UsageError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let program = std::iter::once(OsString::from("njvm"));
+        let args = program.chain(args.into_iter().map(Into::into));
+        Ok(Args::try_parse_from(args)?)
+    }
+}
+
+/// An invalid command line, reported with the messages of the reference
+/// implementation.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum UsageError {
+    UnknownOption(String),
+    MissingValue(&'static str),
+    IllegalValue(&'static str),
+    MoreThanOneCodeFile,
+    Conflict(String, String),
+    Other(String),
+}
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsageError::UnknownOption(option) => {
+                write!(
+                    f,
+                    "Error: unknown option '{option}', try './njvm --help'"
+                )
+            }
+            UsageError::MissingValue(value) => {
+                write!(f, "Error: {value} is missing")
+            }
+            UsageError::IllegalValue(value) => {
+                write!(f, "Error: illegal {value}")
+            }
+            UsageError::MoreThanOneCodeFile => {
+                write!(f, "Error: more than one code file specified")
+            }
+            UsageError::Conflict(option, other) => {
+                write!(
+                    f,
+                    "Error: option '{option}' cannot be used with '{other}'"
+                )
+            }
+            UsageError::Other(message) => write!(f, "Error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+impl From<clap::Error> for UsageError {
+    fn from(error: clap::Error) -> Self {
+        let context = |kind| match error.get(kind) {
+            Some(ContextValue::String(value)) => value.as_str(),
+            _ => "",
+        };
+        let arg = context(ContextKind::InvalidArg);
+        let value = match arg.split_whitespace().next() {
+            Some("--stack") => "stack size",
+            Some("--heap") => "heap size",
+            Some("--profile") => "version profile",
+            Some("--max-steps") => "step limit",
+            _ => "",
+        };
+        match error.kind() {
+            ErrorKind::UnknownArgument if arg.starts_with('-') => {
+                UsageError::UnknownOption(String::from(arg))
+            }
+            ErrorKind::UnknownArgument => UsageError::MoreThanOneCodeFile,
+            ErrorKind::ArgumentConflict => {
+                let prior = match error.get(ContextKind::PriorArg) {
+                    Some(ContextValue::String(prior)) => prior.as_str(),
+                    Some(ContextValue::Strings(prior)) => {
+                        prior.first().map_or("", String::as_str)
+                    }
+                    _ => "",
+                };
+                let option = |arg: &str| {
+                    String::from(arg.split_whitespace().next().unwrap_or(""))
+                };
+                UsageError::Conflict(option(arg), option(prior))
+            }
+            ErrorKind::InvalidValue | ErrorKind::ValueValidation
+                if !value.is_empty() =>
+            {
+                if context(ContextKind::InvalidValue).is_empty() {
+                    UsageError::MissingValue(value)
+                } else {
+                    UsageError::IllegalValue(value)
+                }
+            }
+            _ => {
+                let message =
+                    error.kind().as_str().unwrap_or("invalid arguments");
+                UsageError::Other(String::from(message))
+            }
+        }
+    }
+}
+
+/// Parses a memory size in KiB, which must be positive.
+fn size(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(0) | Err(_) => Err(format!("illegal size '{value}'")),
+        Ok(size) => Ok(size),
+    }
+}
+
+/// Parses a heap size in KiB, which must be positive and at most
+/// [`MAX_HEAP_MEMORY`].
+fn heap_size(value: &str) -> Result<usize, String> {
+    match size(value)? {
+        size if size > MAX_HEAP_MEMORY => {
+            Err(format!("illegal size '{value}'"))
+        }
+        size => Ok(size),
+    }
+}
+
+/// Parses a step limit, which must be positive.
+fn steps(value: &str) -> Result<u64, String> {
+    match value.parse() {
+        Ok(0) | Err(_) => Err(format!("illegal step limit '{value}'")),
+        Ok(steps) => Ok(steps),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let args = Args::try_parse_args([
+            "--stack",
+            "16",
+            "--heap",
+            "4",
+            "--heap",
+            "32",
+            "--gcstats",
+            "prog.bin",
+        ])
+        .unwrap();
+        assert_eq!(args.stack, Some(16));
+        assert_eq!(args.heap, Some(32));
+        assert!(args.gcstats);
+        assert!(!args.gcpurge);
+        assert_eq!(args.file.as_deref(), Some("prog.bin"));
+        let args = Args::try_parse_args(["--debug", "-"]).unwrap();
+        assert!(args.debug);
+        assert_eq!(args.file.as_deref(), Some("-"));
+        let args = Args::try_parse_args(["--verify", "prog.bin"]).unwrap();
+        assert!(args.verify);
+        assert_eq!(args.command, None);
+        let args = Args::try_parse_args(["--map", "prog.map", "-"]).unwrap();
+        assert_eq!(args.map.as_deref(), Some("prog.map"));
+        let args = Args::try_parse_args(["--max-steps", "1000", "-"]).unwrap();
+        assert_eq!(args.max_steps, Some(1000));
+        let args = Args::try_parse_args(["verify", "prog.bin"]).unwrap();
+        let file = String::from("prog.bin");
+        assert_eq!(args.command, Some(Command::Verify { file }));
+        assert_eq!(args.file, None);
+        let args = Args::try_parse_args(["asm", "prog.asm", "-o", "prog.bin"])
+            .unwrap();
+        let command = Command::Asm {
+            input: String::from("prog.asm"),
+            output: String::from("prog.bin"),
+            map: None,
+            debug_info: false,
+        };
+        assert_eq!(args.command, Some(command));
+        let args =
+            Args::try_parse_args(["disasm", "prog.bin", "--map", "m"]).unwrap();
+        let command = Command::Disasm {
+            input: String::from("prog.bin"),
+            output: None,
+            map: Some(String::from("m")),
+        };
+        assert_eq!(args.command, Some(command));
+    }
 
-    /// The file to execute
-    file: Option<String>,
+    #[test]
+    fn test_reference_messages() {
+        let message =
+            |args: &[&str]| Args::try_parse_args(args).unwrap_err().to_string();
+        assert_eq!(
+            message(&["--foo"]),
+            "Error: unknown option '--foo', try './njvm --help'"
+        );
+        assert_eq!(message(&["--stack"]), "Error: stack size is missing");
+        assert_eq!(message(&["--stack", "abc"]), "Error: illegal stack size");
+        assert_eq!(message(&["--stack", "0"]), "Error: illegal stack size");
+        assert_eq!(message(&["--heap"]), "Error: heap size is missing");
+        assert_eq!(message(&["--heap", "-5"]), "Error: illegal heap size");
+        let args = Args::try_parse_args(["--heap", "1048576"]).unwrap();
+        assert_eq!(args.heap, Some(MAX_HEAP_MEMORY));
+        assert_eq!(message(&["--heap", "1048577"]), "Error: illegal heap size");
+        assert_eq!(
+            message(&["--heap", "999999999999"]),
+            "Error: illegal heap size"
+        );
+        assert_eq!(
+            message(&["--heap", "99999999999999999999999"]),
+            "Error: illegal heap size"
+        );
+        assert_eq!(message(&["--max-steps"]), "Error: step limit is missing");
+        assert_eq!(message(&["--max-steps", "0"]), "Error: illegal step limit");
+        assert_eq!(
+            message(&["--debug", "--max-steps", "5", "a.bin"]),
+            "Error: option '--debug' cannot be used with '--max-steps'"
+        );
+        assert_eq!(
+            message(&["--max-steps", "5", "--debug", "a.bin"]),
+            "Error: option '--max-steps' cannot be used with '--debug'"
+        );
+        assert_eq!(
+            message(&["a.bin", "b.bin"]),
+            "Error: more than one code file specified"
+        );
+    }
 }
@@ -0,0 +1,519 @@
+use std::collections::HashMap;
+use std::num::IntErrorKind;
+
+use bigint::BigInt;
+
+use crate::asm::symbols::SymbolMap;
+use crate::cpu::immediate::Immediate;
+use crate::cpu::instruction::Instruction;
+use crate::cpu::opcode::Opcode;
+use crate::cpu::profile::VersionProfile;
+use crate::error::AsmError;
+use crate::io::constant_pool::ConstantPool;
+use crate::io::debug_info::{procedure_start, DebugInfo, Frame};
+use crate::io::header::Header;
+use crate::memory::instruction_register::Bytecode;
+
+/// The range of values that fit into the 24 bit immediate of an
+/// instruction.
+const IMMEDIATE_RANGE: std::ops::RangeInclusive<i64> = -0x800000..=0x7fffff;
+
+/// An assembled program together with its constants, labels, variable
+/// names and source lines.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Program {
+    pub header: Header,
+    pub code: Vec<Bytecode>,
+    pub constants: ConstantPool,
+    pub debug_info: DebugInfo,
+}
+
+impl Program {
+    /// Returns the Ninja binary of the program, with its constant pool if
+    /// it uses `pushk`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header.to_bytes().to_vec();
+        for bytecode in &self.code {
+            bytes.extend_from_slice(&bytecode.to_le_bytes());
+        }
+        if !self.constants.is_empty() {
+            bytes.extend_from_slice(&self.constants.to_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the Ninja binary of the program followed by its debug
+    /// information.
+    pub fn to_bytes_with_debug_info(&self) -> Vec<u8> {
+        let mut bytes = self.to_bytes();
+        bytes.extend_from_slice(&self.debug_info.to_bytes());
+        bytes
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+enum Token<'a> {
+    Name(&'a str),
+    Directive(&'a str),
+    Colon,
+    Number(Immediate),
+    /// A decimal number beyond the range of an immediate value.
+    Constant(&'a str),
+}
+
+/// The operand of an instruction before labels and constants are
+/// resolved.
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Operand<'a> {
+    Value(Immediate),
+    Label(&'a str),
+    Constant(BigInt),
+}
+
+/// Translates Ninja assembler source into a program.
+///
+/// Every line holds any number of `label:` definitions followed by an
+/// instruction, a directive or nothing, and `//` starts a comment that runs
+/// to the end of the line. Immediate values are decimal, `0x`
+/// hexadecimal or character constants like `'\n'`, and the targets of
+/// `jmp`, `brf`, `brt` and `call` are labels. `.vers n` selects the
+/// version of the binary, which is the current one otherwise. The number of
+/// globals is one more than the highest index used by `pushg` and `popg`,
+/// or at least n with `.globals n`.
+///
+/// The operand of `pushk` is the number to push itself rather than its
+/// index: the number is placed in the constant pool of the program, which
+/// assigns its index. `pushk` is only defined from version 9 on, which has
+/// to be selected with `.vers 9`, and `pushc` takes immediate values only.
+///
+/// `.global n name` names global variable n and `.local n name` names the
+/// local variable at fp + n of the procedure that contains it, for the
+/// debug information of the program.
+pub fn assemble(source: &str) -> Result<Program, AsmError> {
+    let mut version = None;
+    let mut global_count = 0;
+    let mut statements = Vec::new();
+    let mut symbols = SymbolMap::new();
+    let mut addresses = HashMap::new();
+    let mut globals = Vec::new();
+    let mut locals = Vec::new();
+
+    for (index, text) in source.lines().enumerate() {
+        let line = index + 1;
+        let tokens = tokenize(line, text)?;
+        let mut tokens = tokens.as_slice();
+        while let [Token::Name(name), Token::Colon, rest @ ..] = tokens {
+            if addresses.insert(*name, statements.len()).is_some() {
+                let name = String::from(*name);
+                return Err(AsmError::DuplicateSymbol { name });
+            }
+            symbols.insert(name, statements.len());
+            tokens = rest;
+        }
+        let syntax = AsmError::Syntax { line };
+        let constant = tokens.iter().find_map(|token| match token {
+            Token::Constant(value) => Some(*value),
+            _ => None,
+        });
+        if let Some(value) = constant {
+            if !matches!(tokens, [Token::Name("pushk"), _]) {
+                let value = String::from(value);
+                return Err(AsmError::ImmediateOutOfRange { line, value });
+            }
+        }
+        let (opcode, operand) = match tokens {
+            [] => continue,
+            [Token::Directive("vers"), Token::Number(number)] => {
+                let profile = u32::try_from(*number)
+                    .ok()
+                    .and_then(VersionProfile::new)
+                    .ok_or(AsmError::UnsupportedVersion {
+                        line,
+                        version: number.to_string(),
+                    })?;
+                version = Some(profile);
+                continue;
+            }
+            [Token::Directive("globals"), Token::Number(count)] => {
+                global_count = u32::try_from(*count).or(Err(syntax))?;
+                continue;
+            }
+            [Token::Directive("global"), Token::Number(index), Token::Name(name)] =>
+            {
+                let index = u32::try_from(*index).or(Err(syntax))?;
+                globals.push((index, String::from(*name)));
+                continue;
+            }
+            [Token::Directive("local"), Token::Number(offset), Token::Name(name)] =>
+            {
+                locals.push((statements.len(), *offset, String::from(*name)));
+                continue;
+            }
+            [Token::Name(name), operand @ ..] => {
+                let opcode = Opcode::from_name(name).ok_or(syntax.clone())?;
+                let operand = match (opcode.is_branch(), operand) {
+                    (_, []) if !opcode.has_immediate() => Operand::Value(0),
+                    (true, [Token::Name(label)]) => Operand::Label(label),
+                    (false, [Token::Number(value)])
+                        if opcode == Opcode::Pushk =>
+                    {
+                        Operand::Constant(BigInt::from(*value))
+                    }
+                    (false, [Token::Number(value)])
+                        if opcode.has_immediate() =>
+                    {
+                        Operand::Value(*value)
+                    }
+                    (false, [Token::Constant(value)])
+                        if opcode == Opcode::Pushk =>
+                    {
+                        Operand::Constant(value.parse().or(Err(syntax))?)
+                    }
+                    _ => return Err(syntax),
+                };
+                (opcode, operand)
+            }
+            _ => return Err(syntax),
+        };
+        statements.push((line, opcode, operand));
+    }
+
+    let profile = version.unwrap_or_else(|| {
+        VersionProfile::new(crate::VERSION).expect("a known version")
+    });
+    let mut code = Vec::with_capacity(statements.len());
+    let mut lines = Vec::with_capacity(statements.len());
+    let mut constants = ConstantPool::new();
+    for (line, opcode, operand) in statements {
+        let immediate = match operand {
+            Operand::Value(value) => value,
+            Operand::Label(label) => match addresses.get(label) {
+                Some(&address) => address as Immediate,
+                None => {
+                    let name = String::from(label);
+                    return Err(AsmError::UndefinedSymbol { name });
+                }
+            },
+            Operand::Constant(constant) => {
+                constants.insert(constant) as Immediate
+            }
+        };
+        let bytecode = Instruction::encode_instruction(opcode, immediate)
+            .map_err(|error| AsmError::ImmediateOutOfRange {
+                line,
+                value: error.value.to_string(),
+            })?;
+        if !profile.supports(bytecode) {
+            return Err(AsmError::UndefinedInstruction {
+                line,
+                opcode,
+                version: profile.version(),
+            });
+        }
+        if matches!(opcode, Opcode::Pushg | Opcode::Popg) && immediate >= 0 {
+            global_count = global_count.max(immediate as u32 + 1);
+        }
+        code.push(bytecode);
+        lines.push(line as u32);
+    }
+
+    let mut frames: Vec<Frame> = Vec::new();
+    for (address, offset, name) in locals {
+        let address = procedure_start(&code, address);
+        let index = match frames.iter().position(|f| f.address == address) {
+            Some(index) => index,
+            None => {
+                frames.push(Frame {
+                    address,
+                    locals: Vec::new(),
+                });
+                frames.len() - 1
+            }
+        };
+        frames[index].locals.push((offset, name));
+    }
+
+    let mut header = Header::new(code.len() as u32, global_count);
+    header.version = profile.version();
+    Ok(Program {
+        header,
+        code,
+        constants,
+        debug_info: DebugInfo {
+            symbols,
+            globals,
+            lines,
+            frames,
+        },
+    })
+}
+
+/// Splits a line into tokens, dropping whitespace and comments.
+fn tokenize(line: usize, text: &str) -> Result<Vec<Token<'_>>, AsmError> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+    while let Some(character) = rest.chars().next() {
+        if rest.starts_with("//") {
+            break;
+        }
+        let (token, length) = match character {
+            ':' => (Token::Colon, 1),
+            '.' => match name_length(&rest[1..]) {
+                0 => return Err(AsmError::Syntax { line }),
+                length => (Token::Directive(&rest[1..=length]), length + 1),
+            },
+            '_' | 'a'..='z' | 'A'..='Z' => {
+                let length = name_length(rest);
+                (Token::Name(&rest[..length]), length)
+            }
+            '-' | '0'..='9' => {
+                let sign = usize::from(character == '-');
+                let length = sign + name_length(&rest[sign..]);
+                (number(line, &rest[..length])?, length)
+            }
+            '\'' => character_constant(line, rest)?,
+            _ => return Err(AsmError::IllegalCharacter { line, character }),
+        };
+        tokens.push(token);
+        rest = rest[length..].trim_start();
+    }
+    Ok(tokens)
+}
+
+/// Returns the length of the name at the start of `text`.
+fn name_length(text: &str) -> usize {
+    text.find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+        .unwrap_or(text.len())
+}
+
+/// Parses a decimal or `0x` hexadecimal number that fits into an
+/// immediate value, or a decimal number of any size as a constant.
+fn number(line: usize, text: &str) -> Result<Token<'_>, AsmError> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let (hexadecimal, parsed) = match digits.strip_prefix("0x") {
+        Some(hex) => (true, i64::from_str_radix(hex, 16)),
+        None => (false, digits.parse()),
+    };
+    let out_of_range = || match hexadecimal {
+        true => Err(AsmError::ImmediateOutOfRange {
+            line,
+            value: String::from(text),
+        }),
+        false => Ok(Token::Constant(text)),
+    };
+    let value = match parsed {
+        Ok(value) if negative => -value,
+        Ok(value) => value,
+        Err(error) => {
+            return match error.kind() {
+                IntErrorKind::PosOverflow => out_of_range(),
+                _ => Err(AsmError::Syntax { line }),
+            };
+        }
+    };
+    if !IMMEDIATE_RANGE.contains(&value) {
+        return out_of_range();
+    }
+    Ok(Token::Number(value as Immediate))
+}
+
+/// Parses the character constant at the start of `text` and returns it as
+/// a number token together with its length.
+fn character_constant(
+    line: usize,
+    text: &str,
+) -> Result<(Token<'static>, usize), AsmError> {
+    let mut characters = text.char_indices().skip(1);
+    let value = match characters.next() {
+        Some((_, '\\')) => match characters.next() {
+            Some((_, 'n')) => '\n',
+            Some((_, 't')) => '\t',
+            Some((_, 'r')) => '\r',
+            Some((_, '0')) => '\0',
+            Some((_, escaped @ ('\\' | '\'' | '"'))) => escaped,
+            _ => return Err(AsmError::Syntax { line }),
+        },
+        Some((_, '\'')) | None => return Err(AsmError::Syntax { line }),
+        Some((_, character)) => character,
+    };
+    match characters.next() {
+        Some((end, '\'')) => Ok((Token::Number(value as Immediate), end + 1)),
+        _ => Err(AsmError::Syntax { line }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Opcode::*;
+
+    fn words(bytes: &[u8]) -> Vec<Bytecode> {
+        bytes
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn test_assemble() {
+        let source = "\
+// adds two numbers
+\t.vers\t4
+__start:\tcall\t_main  // run
+L1: halt
+_main:
+\tpushc\t0x10
+\tpushc\t'\\n'
+\tadd
+\tpopg\t2
+\tret
+";
+        let program = assemble(source).unwrap();
+        let expected = [
+            (Call, 2),
+            (Halt, 0),
+            (Pushc, 16),
+            (Pushc, 10),
+            (Add, 0),
+            (Popg, 2),
+            (Ret, 0),
+        ]
+        .map(|(opcode, immediate)| {
+            Instruction::encode_instruction(opcode, immediate).unwrap()
+        });
+        assert_eq!(program.code, expected);
+        assert_eq!(program.header.version, 4);
+        assert_eq!(program.header.instruction_count, 7);
+        assert_eq!(program.header.global_count, 3);
+        let map = program.debug_info.symbols.to_string();
+        assert_eq!(map, "__start\t0\nL1\t1\n_main\t2\n");
+        let bytes = program.to_bytes();
+        assert_eq!(Header::split(&bytes).unwrap().header, program.header);
+        assert_eq!(program.debug_info.lines, [3, 4, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_assemble_assets() {
+        let mut count = 0;
+        for assignment in ["a1", "a2", "a3", "a4", "a7", "a8"] {
+            let directory = format!("assets/{assignment}");
+            for entry in std::fs::read_dir(directory).unwrap() {
+                let path = entry.unwrap().path();
+                if path.extension().is_none_or(|extension| extension != "asm") {
+                    continue;
+                }
+                let Ok(binary) = std::fs::read(path.with_extension("bin"))
+                else {
+                    continue;
+                };
+                let source = std::fs::read_to_string(&path).unwrap();
+                let program = assemble(&source).unwrap();
+                let parsed = Header::split(&binary).unwrap();
+                assert_eq!(program.code, words(parsed.code), "{path:?}");
+                let global_count = parsed.header.global_count;
+                assert_eq!(program.header.global_count, global_count);
+                if source.contains(".vers") {
+                    assert_eq!(program.to_bytes(), binary, "{path:?}");
+                }
+                count += 1;
+            }
+        }
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn test_debug_info() {
+        let source = "\
+\t.global\t0\tcount
+__start:
+\tcall\t_inc
+\thalt
+_inc:
+\t.local\t-3\tstep
+\tasf\t1
+\t.local\t0\ttemp
+\tpushl\t-3
+\tpushg\t0
+\tadd
+\tpopg\t0
+\trsf
+\tret
+";
+        let program = assemble(source).unwrap();
+        let debug_info = &program.debug_info;
+        assert_eq!(debug_info.global(0), Some("count"));
+        let frame = debug_info.frame(&program.code, 4).unwrap();
+        assert_eq!(frame.address, 2);
+        let names = [(-3, String::from("step")), (0, String::from("temp"))];
+        assert_eq!(frame.locals, names);
+        assert_eq!(debug_info.frame(&program.code, 1), None);
+        let bytes = program.to_bytes_with_debug_info();
+        let parsed = Header::split(&bytes).unwrap();
+        assert_eq!(parsed.debug_info.as_ref(), Some(debug_info));
+    }
+
+    #[test]
+    fn test_constants() {
+        let source = "\
+\t.vers\t9
+\tpushc\t8388607
+\tpushk\t8388608
+\tpushk\t-123456789012345678901234567890
+\tpushk\t8388608
+\thalt
+";
+        let program = assemble(source).unwrap();
+        let expected = [(Pushc, 8388607), (Pushk, 0), (Pushk, 1), (Pushk, 0)];
+        let expected = expected.map(|(opcode, immediate)| {
+            Instruction::encode_instruction(opcode, immediate).unwrap()
+        });
+        assert_eq!(program.code[..4], expected);
+        assert_eq!(program.header.version, 9);
+        assert_eq!(program.constants.len(), 2);
+        let big = program.constants.get(1).unwrap().to_string();
+        assert_eq!(big, "-123456789012345678901234567890");
+        let bytes = program.to_bytes();
+        let parsed = Header::split(&bytes).unwrap();
+        assert_eq!(parsed.constants, Some(program.constants));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source| assemble(source).unwrap_err().to_string();
+        assert_eq!(error("\tpushc\n"), "syntax error in line 1");
+        assert_eq!(error("\n\tadd 1\n"), "syntax error in line 2");
+        assert_eq!(error("\tpush 1\n"), "syntax error in line 1");
+        assert_eq!(error("\tjmp 0\n"), "syntax error in line 1");
+        assert_eq!(error("\tpushc \"a\"\n"), "line 1, illegal character 0x22");
+        assert_eq!(
+            error("\tasf 8388608\n"),
+            "line 1, immediate value 8388608 out of range"
+        );
+        assert_eq!(
+            error("\tpushc 0x800000\n"),
+            "line 1, immediate value 0x800000 out of range"
+        );
+        assert_eq!(error("\tjmp L\n"), "undefined symbol 'L'");
+        assert_eq!(error("L:\nL: halt\n"), "symbol 'L' defined more than once");
+        assert_eq!(error(".globals -1\n"), "syntax error in line 1");
+        assert_eq!(
+            error(".vers 10\n"),
+            "line 1, unsupported Ninja version 10, expected 1 to 9"
+        );
+        assert_eq!(
+            error("\tpushc 8388608\n"),
+            "line 1, immediate value 8388608 out of range"
+        );
+        assert_eq!(
+            error("\tpushk 8388608\n"),
+            "line 1, instruction pushk is not defined in version 8"
+        );
+        assert_eq!(
+            error(".vers 2\n\teq\n"),
+            "line 2, instruction eq is not defined in version 2"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::asm::symbols::{is_label, SymbolMap};
+use crate::cpu::instruction::Instruction;
+use crate::cpu::opcode::Opcode;
+use crate::io::constant_pool::ConstantPool;
+use crate::io::debug_info::DebugInfo;
+use crate::io::header::Header;
+use crate::memory::instruction_register::Bytecode;
+
+/// Translates the instructions of a Ninja binary back into assembler
+/// source that assembles to the same binary.
+///
+/// Every label of `symbols` is defined at its address, and branch targets
+/// without one are named after their address, `F_5` for the target of a
+/// `call` and `L23` for any other. `pushk` shows its number from
+/// `constants` and the variable names of `debug_info` become `.global` and
+/// `.local` directives.
+///
+/// # Panics
+///
+/// Panics unless `code` has been checked like a loaded binary: if it holds
+/// an undefined opcode, a branch target outside the program or a `pushk`
+/// whose constant `constants` lacks.
+pub fn disassemble(
+    header: &Header,
+    code: &[Bytecode],
+    constants: &ConstantPool,
+    symbols: &SymbolMap,
+    debug_info: &DebugInfo,
+) -> String {
+    let instructions: Vec<Instruction> = code
+        .iter()
+        .map(|&bytecode| Instruction::from(bytecode))
+        .collect();
+    let mut labels = vec![Vec::new(); code.len() + 1];
+    let mut names = HashSet::new();
+    for (name, address) in symbols.iter() {
+        if address < labels.len() && is_label(name) && names.insert(name) {
+            labels[address].push(String::from(name));
+        }
+    }
+    let calls = instructions.iter().filter(|i| i.opcode == Opcode::Call);
+    let branches = instructions
+        .iter()
+        .filter(|i| i.opcode.is_branch() && i.opcode != Opcode::Call);
+    for instruction in calls.chain(branches) {
+        let target = instruction.immediate as usize;
+        if labels[target].is_empty() {
+            let prefix = match instruction.opcode {
+                Opcode::Call => "F_",
+                _ => "L",
+            };
+            let mut name = format!("{prefix}{target}");
+            while names.contains(name.as_str()) {
+                name.push('_');
+            }
+            labels[target].push(name);
+        }
+    }
+
+    let mut source = String::new();
+    let _ = writeln!(source, "\t.vers\t{}", header.version);
+    let used_globals = instructions
+        .iter()
+        .filter(|i| matches!(i.opcode, Opcode::Pushg | Opcode::Popg))
+        .filter_map(|i| u32::try_from(i.immediate).ok())
+        .map(|index| index + 1)
+        .max()
+        .unwrap_or(0);
+    if header.global_count > used_globals {
+        let _ = writeln!(source, "\t.globals\t{}", header.global_count);
+    }
+    for (index, name) in &debug_info.globals {
+        let _ = writeln!(source, "\t.global\t{index}\t{name}");
+    }
+    for (address, instruction) in instructions.iter().enumerate() {
+        for label in &labels[address] {
+            let _ = writeln!(source, "{label}:");
+        }
+        let frames = debug_info.frames.iter().filter(|f| f.address == address);
+        for (offset, name) in frames.flat_map(|frame| &frame.locals) {
+            let _ = writeln!(source, "\t.local\t{offset}\t{name}");
+        }
+        let Instruction { opcode, immediate } = *instruction;
+        if opcode.is_branch() {
+            let target = &labels[immediate as usize][0];
+            let _ = writeln!(source, "\t{opcode}\t{target}");
+        } else if opcode == Opcode::Pushk {
+            let constant = &constants
+                .get(immediate as usize)
+                .expect("pushk constant outside the constant pool");
+            let _ = writeln!(source, "\t{opcode}\t{constant}");
+        } else if opcode.has_immediate() {
+            let _ = writeln!(source, "\t{opcode}\t{immediate}");
+        } else {
+            let _ = writeln!(source, "\t{opcode}");
+        }
+    }
+    for label in &labels[code.len()] {
+        let _ = writeln!(source, "{label}:");
+    }
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assembler::assemble;
+
+    #[test]
+    fn test_disassemble() {
+        let source = "\
+\t.vers\t4
+\t.global\t0\tflag
+\tcall\tproc
+\thalt
+proc:
+\t.local\t-3\targ
+\tpushc\t-1
+\tpopg\t0
+\tpushg\t0
+\tbrf\tproc
+\tjmp\tend
+end:
+\tret
+";
+        let program = assemble(source).unwrap();
+        let expected = "\
+\t.vers\t4
+\tcall\tF_2
+\thalt
+F_2:
+\tpushc\t-1
+\tpopg\t0
+\tpushg\t0
+\tbrf\tF_2
+\tjmp\tL7
+L7:
+\tret
+";
+        let (header, code) = (&program.header, &program.code);
+        let constants = &program.constants;
+        let debug_info = DebugInfo::default();
+        let result = disassemble(
+            header,
+            code,
+            constants,
+            &SymbolMap::new(),
+            &debug_info,
+        );
+        assert_eq!(result, expected);
+        let debug_info = &program.debug_info;
+        let symbols = &debug_info.symbols;
+        let result = disassemble(header, code, constants, symbols, debug_info);
+        assert_eq!(result, source);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let source = std::fs::read_to_string("assets/a7/listrev.asm").unwrap();
+        let program = assemble(&source).unwrap();
+        let (header, code) = (&program.header, &program.code);
+        let constants = &program.constants;
+        let debug_info = DebugInfo::default();
+        for symbols in [&SymbolMap::new(), &program.debug_info.symbols] {
+            let result =
+                disassemble(header, code, constants, symbols, &debug_info);
+            let reassembled = assemble(&result).unwrap();
+            assert_eq!(reassembled.to_bytes(), program.to_bytes());
+        }
+        let mut header = program.header;
+        header.global_count += 2;
+        let symbols = SymbolMap::new();
+        let result =
+            disassemble(&header, code, constants, &symbols, &debug_info);
+        let reassembled = assemble(&result).unwrap();
+        assert_eq!(reassembled.header, header);
+    }
+
+    #[test]
+    fn test_constants() {
+        let source = "\
+\t.vers\t9
+\tpushk\t10000000
+\tpushk\t-123456789012345678901234567890
+\tpushk\t7
+\tpushk\t10000000
+\twrint
+\thalt
+";
+        let program = assemble(source).unwrap();
+        let expected = "\
+\t.vers\t9
+\tpushk\t10000000
+\tpushk\t-123456789012345678901234567890
+\tpushk\t7
+\tpushk\t10000000
+\twrint
+\thalt
+";
+        let (header, code) = (&program.header, &program.code);
+        let constants = &program.constants;
+        let symbols = SymbolMap::new();
+        let debug_info = DebugInfo::default();
+        let result =
+            disassemble(header, code, constants, &symbols, &debug_info);
+        assert_eq!(result, expected);
+        let reassembled = assemble(&result).unwrap();
+        assert_eq!(reassembled.to_bytes(), program.to_bytes());
+    }
+}
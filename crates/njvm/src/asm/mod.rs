@@ -0,0 +1,197 @@
+pub mod assembler;
+pub mod disassembler;
+pub mod symbols;
+
+use std::fmt::Debug;
+use std::io::{BufRead, Write};
+
+use crate::asm::symbols::SymbolMap;
+use crate::error::VmError;
+use crate::io::header::Header;
+use crate::memory::stack::StackSlot;
+use crate::NinjaVM;
+
+impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
+    /// Names addresses after the labels of `symbols` in the debugger and in
+    /// the errors of the running program, instead of the labels in the
+    /// debug information of the loaded binary.
+    pub fn set_symbols(&mut self, symbols: SymbolMap) {
+        self.symbols = symbols;
+    }
+
+    /// The labels of the symbol map, or else of the debug information.
+    pub(crate) fn symbol_map(&self) -> &SymbolMap {
+        if self.symbols.is_empty() {
+            &self.debug_info.symbols
+        } else {
+            &self.symbols
+        }
+    }
+
+    /// Returns `address` relative to the nearest label before it, or the
+    /// address itself if there is none.
+    pub(crate) fn location(&self, address: usize) -> String {
+        self.symbol_map()
+            .symbolize(address)
+            .unwrap_or_else(|| address.to_string())
+    }
+
+    /// Returns the location of `address` followed by its source line, if
+    /// the debug information has one.
+    fn source_location(&self, address: usize) -> String {
+        let location = self.location(address);
+        match self.debug_info.lines.get(address) {
+            Some(line) => format!("{location} (line {line})"),
+            None => location,
+        }
+    }
+
+    /// Adds the locations of the current instruction and of the calls of
+    /// the active procedures to an error of the running program, if a
+    /// symbol map or debug information is loaded. Below the frame pointer
+    /// of every procedure lie the frame pointer of its caller and the
+    /// return address.
+    pub(crate) fn traced(&self, error: VmError) -> VmError {
+        if self.symbol_map().is_empty() && self.debug_info.lines.is_empty() {
+            return error;
+        }
+        let number = |index: usize| match self.stack.data.get(index) {
+            Some(StackSlot::Number(number)) => usize::try_from(*number).ok(),
+            _ => None,
+        };
+        let mut trace = vec![self.source_location(self.address())];
+        let mut fp = self.stack.fp;
+        while fp >= 2 {
+            let (Some(ra), Some(caller)) = (number(fp - 2), number(fp - 1))
+            else {
+                break;
+            };
+            trace.push(self.source_location(ra.saturating_sub(1)));
+            if caller >= fp {
+                break;
+            }
+            fp = caller;
+        }
+        VmError::Traced {
+            error: Box::new(error),
+            trace,
+        }
+    }
+
+    /// Assembles the source file `input`, which is read from stdin if it is
+    /// `-`, into the code file `output`, followed by the debug information
+    /// if `debug_info` is set, and writes the symbol map to `map` if given.
+    pub fn assemble_file(
+        &mut self,
+        input: &str,
+        output: &str,
+        map: Option<&str>,
+        debug_info: bool,
+    ) -> Result<(), VmError> {
+        let source = self.read_binary(input)?;
+        let source = String::from_utf8_lossy(&source);
+        let program = assembler::assemble(&source)?;
+        let bytes = if debug_info {
+            program.to_bytes_with_debug_info()
+        } else {
+            program.to_bytes()
+        };
+        std::fs::write(output, bytes)
+            .map_err(|err| VmError::io("write code file", err))?;
+        if let Some(map) = map {
+            std::fs::write(map, program.debug_info.symbols.to_string())
+                .map_err(|err| VmError::io("write map file", err))?;
+        }
+        Ok(())
+    }
+
+    /// Disassembles the code file `input`, which is read from stdin if it
+    /// is `-`, into the assembler file `output` or onto stdout, naming
+    /// labels after the symbol map `map` if given or else after the debug
+    /// information of the binary.
+    pub fn disassemble_file(
+        &mut self,
+        input: &str,
+        output: Option<&str>,
+        map: Option<&str>,
+    ) -> Result<(), VmError> {
+        let map = map.map(read_map).transpose()?;
+        let file = self.read_binary(input)?;
+        let instructions = self.check_binary(file.clone(), input)?;
+        let header = Header::parse(&file).expect("the header was checked");
+        let code: Vec<u32> = instructions
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        let symbols = map.as_ref().unwrap_or(&self.debug_info.symbols);
+        let source = disassembler::disassemble(
+            &header,
+            &code,
+            &self.constants,
+            symbols,
+            &self.debug_info,
+        );
+        match output {
+            Some(output) => std::fs::write(output, source)
+                .map_err(|err| VmError::io("write assembler file", err)),
+            None => {
+                self.io.write_stdout(&source);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Reads and parses the symbol map file `file`.
+pub fn read_map(file: &str) -> Result<SymbolMap, VmError> {
+    let text = std::fs::read_to_string(file)
+        .map_err(|err| VmError::io("read map file", err))?;
+    Ok(SymbolMap::parse(&text)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::InputOutput;
+
+    #[test]
+    fn test_traced_error() {
+        let source = "\
+__start:
+\tpushc\t1
+\tcall\t_div
+\thalt
+_div:
+\tasf\t0
+\tpushl\t-3
+\tpushc\t0
+\tdiv
+\trsf
+\tret
+";
+        let program = assembler::assemble(source).unwrap();
+        let run = |symbols, bytes: &[u8]| {
+            let mut vm = NinjaVM::new(InputOutput::new(
+                &b""[..],
+                Vec::new(),
+                std::io::stderr(),
+            ));
+            vm.load_bytes(bytes).unwrap();
+            vm.set_symbols(symbols);
+            vm.init();
+            vm.work().unwrap_err().to_string()
+        };
+        let bytes = program.to_bytes();
+        let symbols = program.debug_info.symbols.clone();
+        assert_eq!(run(SymbolMap::new(), &bytes), "Division by zero error");
+        assert_eq!(
+            run(symbols, &bytes),
+            "Division by zero error\n    at _div+3\n    at __start+1"
+        );
+        let bytes = program.to_bytes_with_debug_info();
+        assert_eq!(
+            run(SymbolMap::new(), &bytes),
+            "Division by zero error\n    at _div+3 (line 9)\n    at __start+1 (line 3)"
+        );
+    }
+}
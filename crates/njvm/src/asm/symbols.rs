@@ -0,0 +1,160 @@
+use std::fmt::{Display, Formatter};
+
+use crate::error::AsmError;
+
+/// The labels of a program and their addresses, in the format of the map
+/// files written by `njvm asm --map` and `nja --map`: a line with the name
+/// and the address of every label, separated by a tab.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct SymbolMap {
+    symbols: Vec<(String, usize)>,
+}
+
+impl SymbolMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a map file. Every name has to be a valid label.
+    pub fn parse(text: &str) -> Result<Self, AsmError> {
+        let mut map = Self::new();
+        for (index, entry) in text.lines().enumerate() {
+            if entry.trim().is_empty() {
+                continue;
+            }
+            let line = index + 1;
+            let mut fields = entry.split_whitespace();
+            let symbol = match (fields.next(), fields.next(), fields.next()) {
+                (Some(name), Some(address), None) if is_label(name) => {
+                    address.parse().ok().map(|address| (name, address))
+                }
+                _ => None,
+            };
+            let (name, address) =
+                symbol.ok_or(AsmError::InvalidMap { line })?;
+            map.insert(name, address);
+        }
+        Ok(map)
+    }
+
+    pub fn insert(&mut self, name: &str, address: usize) {
+        self.symbols.push((String::from(name), address));
+    }
+
+    /// Returns the address of the label `name`.
+    pub fn address(&self, name: &str) -> Option<usize> {
+        self.symbols
+            .iter()
+            .find(|(symbol, _)| symbol == name)
+            .map(|&(_, address)| address)
+    }
+
+    /// Returns the first label defined at `address`.
+    pub fn name(&self, address: usize) -> Option<&str> {
+        self.names(address).next()
+    }
+
+    /// Returns every label defined at `address`.
+    pub fn names(&self, address: usize) -> impl Iterator<Item = &str> {
+        self.iter()
+            .filter(move |&(_, at)| at == address)
+            .map(|(name, _)| name)
+    }
+
+    /// Returns the label at or nearest before `address` together with the
+    /// distance of `address` from it.
+    pub fn locate(&self, address: usize) -> Option<(&str, usize)> {
+        self.iter()
+            .filter(|&(_, at)| at <= address)
+            .fold(None, |nearest, (name, at)| match nearest {
+                Some((_, best)) if best >= at => nearest,
+                _ => Some((name, at)),
+            })
+            .map(|(name, at)| (name, address - at))
+    }
+
+    /// Returns `address` relative to the nearest label before it, like
+    /// `_fib+3`, or `_fib` for the address of the label itself.
+    pub fn symbolize(&self, address: usize) -> Option<String> {
+        self.locate(address).map(|(name, offset)| match offset {
+            0 => String::from(name),
+            _ => format!("{name}+{offset}"),
+        })
+    }
+
+    /// Returns the address of a label or of a label with an offset, like
+    /// `_fib+3`.
+    pub fn resolve(&self, text: &str) -> Option<usize> {
+        let (name, offset) = match text.split_once('+') {
+            Some((name, offset)) => (name, offset.parse().ok()?),
+            None => (text, 0),
+        };
+        self.address(name.trim_end())?.checked_add(offset)
+    }
+
+    /// Returns the labels and their addresses in the order of their
+    /// definition.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.symbols
+            .iter()
+            .map(|(name, address)| (name.as_str(), *address))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+impl Display for SymbolMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (name, address) in self.iter() {
+            writeln!(f, "{name}\t{address}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether `name` can be used as a label in assembler source.
+pub fn is_label(name: &str) -> bool {
+    let mut characters = name.chars();
+    characters
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && characters.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let map = SymbolMap::parse("__start\t0\n_main\t5\n__1\t5\n\n").unwrap();
+        assert_eq!(map.address("_main"), Some(5));
+        assert_eq!(map.address("_exit"), None);
+        assert_eq!(map.name(5), Some("_main"));
+        assert_eq!(map.names(5).collect::<Vec<_>>(), ["_main", "__1"]);
+        assert_eq!(map.to_string(), "__start\t0\n_main\t5\n__1\t5\n");
+    }
+
+    #[test]
+    fn test_symbolize() {
+        let map = SymbolMap::parse("_main\t5\n__1\t5\n_fib\t9\n").unwrap();
+        assert_eq!(map.symbolize(4), None);
+        assert_eq!(map.symbolize(5).as_deref(), Some("_main"));
+        assert_eq!(map.symbolize(8).as_deref(), Some("_main+3"));
+        assert_eq!(map.symbolize(12).as_deref(), Some("_fib+3"));
+        assert_eq!(map.resolve("_fib"), Some(9));
+        assert_eq!(map.resolve("_main+2"), Some(7));
+        assert_eq!(map.resolve("_exit"), None);
+        assert_eq!(map.resolve("_main+x"), None);
+    }
+
+    #[test]
+    fn test_invalid_map() {
+        let error = SymbolMap::parse("_main\t5\n_exit\n").unwrap_err();
+        assert_eq!(error, AsmError::InvalidMap { line: 2 });
+        let error = SymbolMap::parse("1abc\t5\n").unwrap_err();
+        assert_eq!(error, AsmError::InvalidMap { line: 1 });
+    }
+}
@@ -0,0 +1,326 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::io::{BufRead, Write};
+use std::ptr::{self, NonNull};
+
+use bigint::{bip, Allocator, BigInt, BigObjRef, BIP};
+
+use crate::cpu::immediate::Immediate;
+use crate::error::{Trap, VmError};
+use crate::memory::heap::{ObjRef, HEADER_SIZE};
+use crate::memory::stack::StackSlot;
+use crate::NinjaVM;
+
+/// Numbers are big integer objects on the heap. The heap serves as the
+/// allocator of the big integer processor, so its operands, results and
+/// temporaries all live on the heap and are reclaimed by the collector.
+impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
+    pub fn new_integer(&mut self, value: Immediate) -> Result<ObjRef, VmError> {
+        let res = self.run_bip(&[], || unsafe {
+            bigint::bigFromInt(value);
+            bip.res
+        })?;
+        Ok(self.heap.object_at(res as *const u8))
+    }
+
+    pub fn push_integer(&mut self, value: Immediate) -> Result<(), VmError> {
+        let object = self.new_integer(value)?;
+        self.stack.push_object(object)?;
+        Ok(())
+    }
+
+    pub fn pop_integer(&mut self) -> Result<Immediate, VmError> {
+        let object = self.stack.pop_object()?;
+        self.integer_value(StackSlot::Object(object))
+    }
+
+    /// Converts a slot to an `Immediate`. Raw numbers such as frame
+    /// pointers are returned as they are.
+    pub fn integer_value(&self, slot: StackSlot) -> Result<Immediate, VmError> {
+        let object = match slot {
+            StackSlot::Number(number) => return Ok(number),
+            StackSlot::Object(object) => object,
+        };
+        Immediate::try_from(self.load_big(object)?)
+            .map_err(|_| Trap::BigIntegerTooBig.into())
+    }
+
+    /// Pops two numbers, runs `operation` on them and pushes the result.
+    pub fn arithmetic(
+        &mut self,
+        operation: unsafe extern "C" fn(),
+    ) -> Result<(), VmError> {
+        let op2 = self.stack.pop_object()?;
+        let op1 = self.stack.pop_object()?;
+        let res = self.run_bip(&[op1, op2], || unsafe {
+            operation();
+            bip.res
+        })?;
+        let result = self.heap.object_at(res as *const u8);
+        self.stack.push_object(result)?;
+        Ok(())
+    }
+
+    /// Pops the divisor and the dividend and returns the quotient and the
+    /// remainder.
+    pub fn division(&mut self) -> Result<(ObjRef, ObjRef), VmError> {
+        let op2 = self.stack.pop_object()?;
+        let op1 = self.stack.pop_object()?;
+        if self.load_big(op2)?.is_zero() {
+            return Err(Trap::DivisionByZero.into());
+        }
+        let (res, rem) = self.run_bip(&[op1, op2], || unsafe {
+            bigint::bigDiv();
+            (bip.res, bip.rem)
+        })?;
+        let quotient = self.heap.object_at(res as *const u8);
+        let remainder = self.heap.object_at(rem as *const u8);
+        Ok((quotient, remainder))
+    }
+
+    /// Pops two numbers and compares them.
+    pub fn comparison(&mut self) -> Result<Ordering, VmError> {
+        let op2 = self.stack.pop_object()?;
+        let op1 = self.stack.pop_object()?;
+        let cmp = self.run_bip(&[op1, op2], || unsafe { bigint::bigCmp() })?;
+        Ok(cmp.cmp(&0))
+    }
+
+    /// Parses a decimal number into a big integer object.
+    pub fn parse_integer(&mut self, decimal: &[u8]) -> Result<ObjRef, VmError> {
+        let big: BigInt = std::str::from_utf8(decimal)
+            .ok()
+            .and_then(|decimal| decimal.parse().ok())
+            .ok_or(Trap::NotAnInteger)?;
+        self.new_big_integer(&big)
+    }
+
+    /// Copies a `BigInt` into a new big integer object.
+    pub fn new_big_integer(&mut self, big: &BigInt) -> Result<ObjRef, VmError> {
+        let data = big.data();
+        let object = self.new_primitive(data.len())?;
+        self.heap.primitive_data_mut(object)?.copy_from_slice(data);
+        Ok(object)
+    }
+
+    /// Formats a big integer object in decimal.
+    pub fn format_integer(&self, object: ObjRef) -> Result<String, VmError> {
+        Ok(self.load_big(object)?.to_string())
+    }
+
+    /// Writes a big integer object in decimal to stdout.
+    pub fn write_integer(&mut self, object: ObjRef) -> Result<(), VmError> {
+        let big = self.load_big(object)?;
+        self.io.print(format_args!("{big}"));
+        Ok(())
+    }
+
+    /// Copies a big integer object from the heap into a `BigInt`.
+    fn load_big(&self, object: ObjRef) -> Result<BigInt, VmError> {
+        if object.is_nil() {
+            return Err(Trap::BigIntegerNilReference.into());
+        }
+        Ok(BigInt::from_data(self.heap.primitive_data(object)?))
+    }
+
+    /// Loads `operands` into the operand registers and runs `operation`
+    /// with the heap as the allocator of the big integer processor.
+    ///
+    /// References to heap objects are only valid until the next
+    /// allocation, so `operation` must return the registers it needs.
+    fn run_bip<T>(
+        &mut self,
+        operands: &[ObjRef],
+        operation: impl FnOnce() -> T,
+    ) -> Result<T, VmError> {
+        let mut registers = [ptr::null_mut(); 2];
+        for (register, operand) in registers.iter_mut().zip(operands) {
+            if operand.is_nil() {
+                return Err(Trap::BigIntegerNilReference.into());
+            }
+            *register = self.heap.primitive_pointer(*operand)? as BigObjRef;
+        }
+        let mut allocator = HeapAllocator {
+            vm: self,
+            overflow: Vec::new(),
+        };
+        let result = bigint::with_allocator(&mut allocator, || {
+            unsafe {
+                bip.op1 = registers[0];
+                bip.op2 = registers[1];
+            }
+            operation()
+        });
+        if allocator.overflow.is_empty() {
+            Ok(result)
+        } else {
+            Err(Trap::HeapOverflow.into())
+        }
+    }
+}
+
+/// Allocates the objects of the big integer processor on the heap.
+///
+/// The library cannot be interrupted, so once the heap is exhausted the
+/// remaining objects of the operation are placed in `overflow` and the
+/// operation fails after it has finished.
+struct HeapAllocator<'a, R: BufRead + Debug, W: Write + Debug, E: Write + Debug>
+{
+    vm: &'a mut NinjaVM<R, W, E>,
+    overflow: Vec<Box<[u32]>>,
+}
+
+impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> Allocator
+    for HeapAllocator<'_, R, W, E>
+{
+    fn new_prim_object(
+        &mut self,
+        data_size: usize,
+        registers: &mut BIP,
+    ) -> NonNull<bigint::ObjRef> {
+        let mut object = self.vm.heap.new_primitive(data_size);
+        if object.is_none() && self.overflow.is_empty() {
+            self.vm.collect_garbage_with_registers(registers);
+            object = self.vm.heap.new_primitive(data_size);
+        }
+        let pointer = match object {
+            Some(object) => self.vm.heap.primitive_pointer(object).ok(),
+            None => None,
+        };
+        let pointer = pointer.unwrap_or_else(|| {
+            let words = (HEADER_SIZE + data_size).div_ceil(4);
+            let mut memory = vec![0u32; words].into_boxed_slice();
+            memory[0] = data_size as u32;
+            let pointer = memory.as_mut_ptr() as *mut u8;
+            self.overflow.push(memory);
+            pointer
+        });
+        NonNull::new(pointer as *mut bigint::ObjRef)
+            .expect("allocated memory is never at address zero")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::InputOutput;
+    use crate::memory::heap::Heap;
+
+    #[test]
+    fn test_integer_round_trip() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        for value in [0, 1, -1, 255, 256, -65536, i32::MAX, i32::MIN + 1] {
+            let object = vm.new_integer(value).unwrap();
+            assert_eq!(
+                vm.integer_value(StackSlot::Object(object)).unwrap(),
+                value
+            );
+            assert_eq!(vm.format_integer(object).unwrap(), value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let decimal = "-123456789012345678901234567890";
+        let object = vm.parse_integer(decimal.as_bytes()).unwrap();
+        assert_eq!(vm.format_integer(object).unwrap(), decimal);
+    }
+
+    #[test]
+    fn test_additions_do_not_exhaust_the_heap() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.heap = Heap::new(1);
+        vm.push_integer(0).unwrap();
+        for _ in 0..1_000_000 {
+            vm.push_integer(1).unwrap();
+            vm.add().unwrap();
+        }
+        assert_eq!(vm.pop_integer().unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn test_collection_relocates_registers() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.heap = Heap::new(1);
+        vm.heap.purge = true;
+        vm.sda.data.resize(1, StackSlot::default());
+        let decimal = "-123456789012345678901234567890";
+        let object = vm.parse_integer(decimal.as_bytes()).unwrap();
+        vm.stack.push_object(object).unwrap();
+        vm.popg(0).unwrap();
+        for _ in 0..1000 {
+            vm.pushg(0).unwrap();
+            vm.pushc(7).unwrap();
+            vm.mul().unwrap();
+            vm.pushc(7).unwrap();
+            vm.div().unwrap();
+            vm.popg(0).unwrap();
+        }
+        vm.pushg(0).unwrap();
+        let object = vm.stack.pop_object().unwrap();
+        assert_eq!(vm.format_integer(object).unwrap(), decimal);
+    }
+
+    #[test]
+    fn test_integer_value_too_big_fails() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let object = vm.parse_integer(b"2147483648").unwrap();
+        let result = vm.integer_value(StackSlot::Object(object));
+        assert!(matches!(result, Err(VmError::Trap(Trap::BigIntegerTooBig))));
+    }
+
+    #[test]
+    fn test_nil_operand_fails() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.pushn().unwrap();
+        vm.pushc(1).unwrap();
+        let result = vm.add();
+        assert!(matches!(
+            result,
+            Err(VmError::Trap(Trap::BigIntegerNilReference))
+        ));
+    }
+}
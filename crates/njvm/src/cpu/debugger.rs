@@ -1,83 +1,62 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 use std::io::{BufRead, Write};
 
+use crate::cpu::instruction::Instruction;
+use crate::cpu::opcode::Opcode;
+use crate::error::VmError;
+use crate::memory::stack::StackSlot;
 use crate::NinjaVM;
 
 impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
-    pub fn debug(&mut self, bin: &str) {
-        let instructions = self.load_binary(bin);
-        self.load_instructions(&instructions);
+    pub fn debug(&mut self, bin: &str) -> Result<(), VmError> {
+        self.load(bin)?;
         let code_size = self.ir.data.len();
         let data_size = self.sda.data.len();
         let message = format!("DEBUG: file '{bin}' loaded (code size = {code_size}, data size = {data_size})\n");
-        self.io_borrow().write_stdout(&message);
+        self.io.write_stdout(&message);
         self.init();
-        self.prompt();
+        self.prompt()
     }
 
-    pub fn test_debug(&mut self, bin: &str) {
-        let instructions = self.load_test_binary(bin);
-        self.load_instructions(&instructions);
-        let code_size = self.ir.data.len();
-        let data_size = self.sda.data.len();
-        let message = format!("DEBUG: file '{bin}' loaded (code size = {code_size}, data size = {data_size})\n");
-        self.io_borrow().write_stdout(&message);
-        self.init();
-        self.prompt();
-    }
-
-    pub fn prompt(&mut self) {
-        self.io_borrow().write_stdout(
+    pub fn prompt(&mut self) -> Result<(), VmError> {
+        self.io.write_stdout(
             "DEBUG: inspect, list, breakpoint, step, run, quit?\n",
         );
         loop {
-            if self.ir.pc == self.ir.data.len() {
-                break;
-            }
-            self.print_next_instruction();
-            let mut input = String::new();
-            if self
-                .io_borrow()
-                .stdin_borrow_mut()
-                .read_line(&mut input)
-                .is_err()
-            {
-                self.io.borrow().fatal_error("Error: could not read line\n")
-            }
+            self.ir.instruction(self.ir.pc).map_err(|error| {
+                self.io.flush_stdout();
+                self.traced(error)
+            })?;
+            self.print_next_instruction()?;
+            let input = self.read_input("read line")?;
             let input = input.trim();
+            let argument = input
+                .split_once(char::is_whitespace)
+                .map(|(_, argument)| argument.trim());
             if let Some(input) = input.chars().next() {
                 match input {
-                    'i' => self.inspect(),
-                    'l' => self.print_ir(),
-                    'b' => self.set_breakpoint(),
-                    's' => self.step(),
-                    'r' => {
-                        self.run();
-                        break;
-                    }
-                    'q' => {
-                        self.halt();
-                        break;
+                    'i' => self.inspect()?,
+                    'l' => self.print_ir()?,
+                    'b' => match argument {
+                        Some(target) => self.set_breakpoint_to(target),
+                        None => self.set_breakpoint()?,
+                    },
+                    's' => {
+                        if self.step_halts()? {
+                            return Ok(());
+                        }
                     }
+                    'r' => return self.run(),
+                    'q' => return self.halt(),
                     _ => continue,
                 }
             }
         }
     }
 
-    pub fn inspect(&mut self) {
-        self.io_borrow()
-            .write_stdout("DEBUG: [inspect]: stack, data?\n");
-        let mut input = String::new();
-        if self
-            .io_borrow()
-            .stdin_borrow_mut()
-            .read_line(&mut input)
-            .is_err()
-        {
-            self.io_borrow()
-                .fatal_error("Error: could not read input\n")
-        }
+    pub fn inspect(&mut self) -> Result<(), VmError> {
+        self.io.write_stdout("DEBUG: [inspect]: stack, data?\n");
+        let input = self.read_input("read input")?;
         let input = input.trim();
         if let Some(input) = input.chars().next() {
             match input {
@@ -86,96 +65,229 @@ impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
                 _ => {}
             }
         }
+        Ok(())
     }
 
-    pub fn step(&mut self) {
-        let instruction = self.ir.data[self.ir.pc];
-        self.ir.pc += 1;
-        self.execute_instruction(instruction);
+    pub fn step(&mut self) -> Result<(), VmError> {
+        let instruction =
+            self.ir.instruction(self.ir.pc).and_then(|instruction| {
+                self.ir.pc += 1;
+                self.execute(instruction)
+            });
+        instruction.map_err(|error| {
+            self.io.flush_stdout();
+            self.traced(error)
+        })
     }
 
-    pub fn run(&mut self) {
+    pub fn run(&mut self) -> Result<(), VmError> {
         loop {
-            if self.ir.pc == self.ir.data.len() {
-                break;
-            }
             if let Some(bp) = self.bp {
                 if bp == self.ir.pc {
                     self.bp = None;
-                    self.io_borrow()
-                        .write_stdout("DEBUG [breakpoint]: cleared\n");
-                    self.prompt();
-                    break;
+                    self.io.write_stdout("DEBUG [breakpoint]: cleared\n");
+                    return self.prompt();
                 }
             }
-            self.step()
+            if self.step_halts()? {
+                return Ok(());
+            }
         }
     }
 
-    pub fn set_breakpoint(&mut self) {
+    /// Steps and returns whether the executed instruction was `halt`.
+    fn step_halts(&mut self) -> Result<bool, VmError> {
+        let halt = self
+            .ir
+            .instruction(self.ir.pc)
+            .is_ok_and(|instruction| instruction.opcode == Opcode::Halt);
+        self.step()?;
+        Ok(halt)
+    }
+
+    pub fn set_breakpoint(&mut self) -> Result<(), VmError> {
         if let Some(bp) = self.bp {
+            let bp = self.location(bp);
             let message =
                 format!("DEBUG [breakpoint]: breakpoint is set at {bp}\n");
-            self.io_borrow().write_stdout(&message);
+            self.io.write_stdout(&message);
         } else {
-            self.io_borrow()
-                .write_stdout("DEBUG [breakpoint]: cleared\n");
+            self.io.write_stdout("DEBUG [breakpoint]: cleared\n");
         }
-        self.io_borrow().write_stdout("DEBUG [breakpoint]: address to set, -1 to clear, <ret> for no change?\n");
-        let mut input = String::new();
-        if self
-            .io_borrow()
-            .stdin_borrow_mut()
-            .read_line(&mut input)
-            .is_err()
-        {
-            self.io_borrow()
-                .fatal_error("Error: could not read input\n")
-        }
-        let bp: isize = match String::from(input.trim()).parse() {
-            Ok(bp) => bp,
-            Err(_) => return,
+        self.io.write_stdout("DEBUG [breakpoint]: address to set, -1 to clear, <ret> for no change?\n");
+        let input = self.read_input("read input")?;
+        self.set_breakpoint_to(input.trim());
+        Ok(())
+    }
+
+    /// Sets the breakpoint to `target`, which is an address, a label like
+    /// `_main` or `_main+3` of the symbol map, or `-1` to clear it.
+    pub fn set_breakpoint_to(&mut self, target: &str) {
+        let bp = match target.parse::<isize>() {
+            Ok(-1) => {
+                self.bp = None;
+                self.io.write_stdout("DEBUG [breakpoint]: now cleared\n");
+                return;
+            }
+            Ok(bp) if bp >= 0 => bp as usize,
+            Ok(_) => return,
+            Err(_) if target.is_empty() => return,
+            Err(_) => match self.symbol_map().resolve(target) {
+                Some(bp) => bp,
+                None => {
+                    let message = format!(
+                        "DEBUG [breakpoint]: unknown symbol '{target}'\n"
+                    );
+                    self.io.write_stdout(&message);
+                    return;
+                }
+            },
         };
-        if bp < -1 {
-            return;
+        self.bp = Some(bp);
+        let location = self.location(bp);
+        let message = format!("DEBUG [breakpoint]: now set at {location}\n");
+        self.io.write_stdout(&message);
+    }
+
+    pub fn print_next_instruction(&mut self) -> Result<(), VmError> {
+        let line = self.listing(self.ir.pc)?;
+        self.io.write_stdout(&line);
+        Ok(())
+    }
+
+    pub fn print_stack(&mut self) {
+        let stack = self.stack.to_string();
+        let locals = self.local_variables();
+        self.print_section("-----------------------------\n", &stack, &locals);
+    }
+
+    pub fn print_sda(&mut self) {
+        let sda = format!("{:#?}", self.sda);
+        let globals = self.global_variables();
+        self.print_section("------------------\n", &sda, &globals);
+    }
+
+    pub fn print_ir(&mut self) -> Result<(), VmError> {
+        self.io.write_stdout("------------------\n");
+        for pc in 0..self.ir.data.len() {
+            let line = self.listing(pc)?;
+            self.io.write_stdout(&line);
         }
-        match bp {
-            -1 => {
-                self.bp = None;
-                self.io_borrow()
-                    .write_stdout("DEBUG [breakpoint]: now cleared\n");
+        self.io.write_stdout("------------------\n");
+        Ok(())
+    }
+
+    /// Prints `dump` between separators, followed by the named `variables`
+    /// if there are any.
+    fn print_section(&mut self, separator: &str, dump: &str, variables: &str) {
+        self.io.write_stdout(separator);
+        self.io.write_stdout(dump);
+        if !variables.is_empty() {
+            if !dump.ends_with('\n') {
+                self.io.write_stdout("\n");
             }
-            _ => {
-                let bp = bp as usize;
-                self.bp = Some(bp);
-                let message = format!("DEBUG [breakpoint]: now set at {bp}\n");
-                self.io_borrow().write_stdout(&message);
+            self.io.write_stdout(separator);
+            self.io.write_stdout(variables);
+        }
+        self.io.write_stdout(separator);
+    }
+
+    /// Formats the instruction at `pc` for the listing, naming its address
+    /// and branch target after the symbol map and adding its source line
+    /// from the debug information.
+    fn listing(&self, pc: usize) -> Result<String, VmError> {
+        let Instruction { opcode, immediate } = self.ir.instruction(pc)?;
+        let symbols = self.symbol_map();
+        let mut line = if symbols.is_empty() {
+            format!("{pc:04}: {opcode} {immediate}")
+        } else {
+            let location =
+                symbols.symbolize(pc).unwrap_or_else(|| format!("{pc:04}"));
+            if opcode.is_branch() {
+                let target = self.location(immediate as usize);
+                format!("{location}: {opcode} {target}")
+            } else {
+                format!("{location}: {opcode} {immediate}")
             }
+        };
+        if let Some(source) = self.debug_info.lines.get(pc) {
+            line.push_str(&format!(" (line {source})"));
         }
+        line.push('\n');
+        Ok(line)
     }
 
-    pub fn print_next_instruction(&mut self) {
-        self.ir.print_instruction(self.ir.pc);
+    /// Formats the named local variables of the current procedure, once
+    /// its frame is allocated.
+    fn local_variables(&self) -> String {
+        let pc = self.ir.pc;
+        let frame = match self.debug_info.frame(&self.ir.data, pc) {
+            Some(frame) if frame.address != pc => frame,
+            _ => return String::new(),
+        };
+        let fp = self.stack.fp as isize;
+        self.variables(frame.locals.iter().map(|(offset, name)| {
+            let slot = usize::try_from(fp + *offset as isize)
+                .ok()
+                .filter(|&index| index < self.stack.sp)
+                .map(|index| self.stack.data[index]);
+            (name.as_str(), slot)
+        }))
     }
 
-    pub fn print_stack(&mut self) {
-        self.io_borrow()
-            .write_stdout("-----------------------------\n");
-        self.stack.print();
-        self.io_borrow()
-            .write_stdout("-----------------------------\n");
+    /// Formats the named global variables.
+    fn global_variables(&self) -> String {
+        self.variables(self.debug_info.globals.iter().map(|(index, name)| {
+            let slot = self.sda.data.get(*index as usize).copied();
+            (name.as_str(), slot)
+        }))
     }
 
-    pub fn print_sda(&mut self) {
-        self.io_borrow().write_stdout("------------------\n");
-        self.sda.print();
-        self.io_borrow().write_stdout("------------------\n");
+    fn variables<'a>(
+        &self,
+        variables: impl Iterator<Item = (&'a str, Option<StackSlot>)>,
+    ) -> String {
+        let mut text = String::new();
+        for (name, slot) in variables {
+            let _ = match slot {
+                Some(slot) => fmt::Write::write_fmt(
+                    &mut text,
+                    format_args!("{name} = {}\n", self.value(slot)),
+                ),
+                None => fmt::Write::write_fmt(
+                    &mut text,
+                    format_args!("{name} = xxxx\n"),
+                ),
+            };
+        }
+        text
     }
 
-    pub fn print_ir(&mut self) {
-        self.io_borrow().write_stdout("------------------\n");
-        self.ir.print();
-        self.io_borrow().write_stdout("------------------\n");
+    /// Formats the value of a variable: numbers as integers and other
+    /// objects as references.
+    fn value(&self, slot: StackSlot) -> String {
+        match slot {
+            StackSlot::Object(object) if object.is_nil() => String::from("nil"),
+            StackSlot::Object(object)
+                if self.heap.is_compound(object).unwrap_or(true) =>
+            {
+                slot.to_string()
+            }
+            _ => match self.integer_value(slot) {
+                Ok(value) => value.to_string(),
+                Err(_) => slot.to_string(),
+            },
+        }
+    }
+
+    fn read_input(&mut self, action: &'static str) -> Result<String, VmError> {
+        self.io.flush_stdout();
+        let mut input = String::new();
+        self.io
+            .stdin()
+            .read_line(&mut input)
+            .map_err(|err| VmError::io(action, err))?;
+        Ok(input)
     }
 }
 
@@ -184,6 +296,7 @@ mod tests {
     use super::*;
 
     use crate::cpu::opcode::Opcode::*;
+    use crate::error::Trap;
     use crate::io::InputOutput;
 
     #[test]
@@ -195,11 +308,11 @@ mod tests {
             &mut output,
             std::io::stderr(),
         ));
-        let instructions = vm.load_test_binary("assets/a3/prog1.bin");
+        let instructions = vm.load_binary("assets/a3/prog1.bin").unwrap();
 
         vm.load_instructions(&instructions);
         vm.init();
-        vm.prompt();
+        vm.prompt().unwrap();
 
         let expected = r#"Ninja Virtual Machine started
 DEBUG: inspect, list, breakpoint, step, run, quit?
@@ -221,23 +334,21 @@ Ninja Virtual Machine stopped
             &mut output,
             std::io::stderr(),
         ));
-        let instructions = vm.load_test_binary("assets/a3/prog1.bin");
+        let instructions = vm.load_binary("assets/a3/prog1.bin").unwrap();
         vm.load_instructions(&instructions);
         vm.init();
-        vm.step();
-        vm.stack.print();
+        vm.step().unwrap();
         assert_eq!(vm.stack.sp, 1);
         assert_eq!(vm.stack.fp, 0);
         assert_eq!(vm.stack.data.len(), 1);
-        assert_eq!(vm.stack.data[0], 9);
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 9);
 
-        let expected = r#"Ninja Virtual Machine started
-sp ---> 0001: xxxx
-fp ---> 0000: 9"#;
+        let expected = r#"sp ---> 0001: xxxx
+fp ---> 0000: (objref) 0x00000000"#;
+        assert_eq!(expected, vm.stack.to_string());
 
         let result = String::from_utf8(output).unwrap();
-
-        assert_eq!(expected, result);
+        assert_eq!("Ninja Virtual Machine started\n", result);
     }
 
     #[test]
@@ -249,11 +360,11 @@ fp ---> 0000: 9"#;
             &mut output,
             std::io::stderr(),
         ));
-        vm.test_debug("assets/a3/prog1.bin");
+        vm.debug("assets/a3/prog1.bin").unwrap();
         assert_eq!(vm.ir.data.len(), 27);
         assert_eq!(vm.sda.data.len(), 2);
-        assert_eq!(vm.sda.data[0], 4);
-        assert_eq!(vm.sda.data[1], 4);
+        assert_eq!(vm.integer_value(vm.sda.data[0]).unwrap(), 4);
+        assert_eq!(vm.integer_value(vm.sda.data[1]).unwrap(), 4);
         assert_eq!(vm.stack.sp, 1);
         assert_eq!(vm.stack.fp, 0);
         assert_eq!(vm.stack.data.len(), 1);
@@ -268,9 +379,9 @@ fp ---> 0000: 9"#;
             &mut output,
             std::io::stderr(),
         ));
-        vm.test_debug("assets/a3/prog1.bin");
+        vm.debug("assets/a3/prog1.bin").unwrap();
         assert_eq!(vm.bp, Some(23));
-        vm.test_debug("assets/a3/prog1.bin");
+        vm.debug("assets/a3/prog1.bin").unwrap();
         assert_eq!(vm.bp, None);
     }
 
@@ -283,7 +394,7 @@ fp ---> 0000: 9"#;
             &mut output,
             std::io::stderr(),
         ));
-        vm.test_debug("assets/a3/prog1.bin");
+        vm.debug("assets/a3/prog1.bin").unwrap();
     }
 
     #[test]
@@ -295,14 +406,107 @@ fp ---> 0000: 9"#;
             &mut output,
             std::io::stderr(),
         ));
-        vm.test_debug("assets/a3/prog1.bin");
+        vm.debug("assets/a3/prog1.bin").unwrap();
         assert_eq!(vm.ir.pc, 5);
         assert_eq!(vm.bp, None);
-        vm.prompt();
+        vm.prompt().unwrap();
         assert_eq!(vm.stack.data.len(), 1);
         assert_eq!(vm.sda.data.len(), 2);
-        assert_eq!(vm.sda.data[0], 4);
-        assert_eq!(vm.sda.data[1], 4);
+        assert_eq!(vm.integer_value(vm.sda.data[0]).unwrap(), 4);
+        assert_eq!(vm.integer_value(vm.sda.data[1]).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_symbols() {
+        let source = std::fs::read_to_string("assets/a3/prog1.asm").unwrap();
+        let program = crate::asm::assembler::assemble(&source).unwrap();
+        let input = b"b L3+1\nb\nL2\nb L9\nr\n8\n12\nl\nq\n";
+        let mut output = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &input[..],
+            &mut output,
+            std::io::stderr(),
+        ));
+        vm.set_symbols(program.debug_info.symbols);
+        vm.debug("assets/a3/prog1.bin").unwrap();
+        assert_eq!(vm.ir.pc, 22);
+        let result = String::from_utf8(output).unwrap();
+        let expected = "\
+DEBUG [breakpoint]: now set at L3+1
+0000: rdint 0
+DEBUG [breakpoint]: breakpoint is set at L3+1
+DEBUG [breakpoint]: address to set, -1 to clear, <ret> for no change?
+DEBUG [breakpoint]: now set at L2
+0000: rdint 0
+DEBUG [breakpoint]: unknown symbol 'L9'
+0000: rdint 0
+";
+        assert!(result.contains(expected), "{result}");
+        assert!(result.contains("L1+3: brf L2\n"), "{result}");
+        assert!(result.contains("L2: pushg 0\n"), "{result}");
+    }
+
+    #[test]
+    fn test_debug_info() {
+        let source = "\
+	.global	0	total
+	pushc	5
+	popg	0
+	pushc	5
+	call	_f
+	drop	1
+	halt
+_f:
+	.local	-3	x
+	.local	0	y
+	asf	1
+	pushc	7
+	popl	0
+	rsf
+	ret
+";
+        let program = crate::asm::assembler::assemble(source).unwrap();
+        let input = b"b _f+3\nr\ni\ns\ni\nd\nq\n";
+        let mut output = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &input[..],
+            &mut output,
+            std::io::stderr(),
+        ));
+        vm.load_bytes(&program.to_bytes_with_debug_info()).unwrap();
+        vm.init();
+        vm.prompt().unwrap();
+        let result = String::from_utf8(output).unwrap();
+        assert!(result.contains("_f+3: rsf 0 (line 14)\n"), "{result}");
+        assert!(result.contains("x = 5\ny = 7\n"), "{result}");
+        assert!(result.contains("total = 5\n"), "{result}");
+    }
+
+    #[test]
+    fn test_past_end() {
+        let source = "\tpushc\t1\n\twrint\n";
+        let program = crate::asm::assembler::assemble(source).unwrap();
+        let empty = crate::io::header::Header::new(0, 0).to_bytes();
+        let cases = [
+            (program.to_bytes(), &b"s\ns\n"[..], 2),
+            (program.to_bytes(), b"r\n", 2),
+            (empty.to_vec(), b"", 0),
+        ];
+        for (bytes, input, end) in cases {
+            let mut vm = NinjaVM::new(InputOutput::new(
+                input,
+                Vec::new(),
+                std::io::stderr(),
+            ));
+            vm.load_bytes(&bytes).unwrap();
+            vm.init();
+            let error = vm.prompt().unwrap_err();
+            let expected = Trap::PastEnd { pc: end };
+            assert!(
+                matches!(error, VmError::Trap(trap) if trap == expected),
+                "{error}"
+            );
+        }
     }
 
     #[test]
@@ -314,14 +518,17 @@ fp ---> 0000: 9"#;
             std::io::stderr(),
         ));
         vm.ir.resize_data(3, 0);
-        vm.ir.register_instruction(Pushc, 1);
-        vm.ir.register_instruction(Pushc, 2);
-        vm.ir.register_instruction(Add, 0);
+        vm.ir.register_instruction(Pushc, 1).unwrap();
+        vm.ir.register_instruction(Pushc, 2).unwrap();
+        vm.ir.register_instruction(Add, 0).unwrap();
         vm.init();
-        vm.print_next_instruction();
+        vm.print_next_instruction().unwrap();
+        vm.ir.pc += 1;
+        vm.print_next_instruction().unwrap();
         vm.ir.pc += 1;
-        vm.print_next_instruction();
+        vm.print_next_instruction().unwrap();
         vm.ir.pc += 1;
-        vm.print_next_instruction();
+        let error = vm.print_next_instruction().unwrap_err();
+        assert!(matches!(error, VmError::Trap(Trap::PastEnd { pc: 3 })));
     }
 }
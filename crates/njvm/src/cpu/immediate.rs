@@ -1,3 +1,4 @@
+use crate::error::ImmediateOutOfRange;
 use crate::memory::instruction_register::Bytecode;
 
 #[macro_export]
@@ -27,7 +28,9 @@ pub trait Decoding {
 }
 
 pub trait Encoding {
-    fn encode(immediate: Immediate) -> Bytecode;
+    /// Encodes `immediate` into the low 24 bits of an instruction. Larger
+    /// numbers belong into the constant pool.
+    fn encode(immediate: Immediate) -> Result<Bytecode, ImmediateOutOfRange>;
 }
 
 impl Decoding for Immediate {
@@ -37,16 +40,16 @@ impl Decoding for Immediate {
 }
 
 impl Encoding for Immediate {
-    fn encode(immediate: Immediate) -> Bytecode {
+    fn encode(immediate: Immediate) -> Result<Bytecode, ImmediateOutOfRange> {
         const MIN: i32 = -8388608;
         const MAX: i32 = 8388607;
         match immediate {
             MIN..=MAX => {
                 let immediate = immediate!(immediate);
                 let bytes = immediate.to_be_bytes();
-                u32::from_be_bytes(bytes)
+                Ok(u32::from_be_bytes(bytes))
             }
-            _ => panic!("Immediate value out of range"),
+            value => Err(ImmediateOutOfRange { value }),
         }
     }
 }
@@ -83,21 +86,22 @@ mod tests {
 
     #[test]
     fn test_encode_immediate() {
-        assert_eq!(Immediate::encode(1), 0x00000001);
-        assert_eq!(Immediate::encode(-1), 0x00ffffff)
+        assert_eq!(Immediate::encode(1), Ok(0x00000001));
+        assert_eq!(Immediate::encode(-1), Ok(0x00ffffff));
+        assert_eq!(Immediate::encode(8388607), Ok(0x007fffff));
+        assert_eq!(Immediate::encode(-8388608), Ok(0x00800000));
     }
 
     #[test]
-    #[should_panic(expected = "Immediate value out of range")]
     fn test_immediate_value_over_range() {
-        std::panic::set_hook(Box::new(|_| {}));
-        Immediate::encode(100000000);
+        let error = Immediate::encode(100000000).unwrap_err();
+        assert_eq!(error, ImmediateOutOfRange { value: 100000000 });
+        assert_eq!(error.to_string(), "immediate value 100000000 out of range");
     }
 
     #[test]
-    #[should_panic(expected = "Immediate value out of range")]
     fn test_immediate_value_under_range() {
-        std::panic::set_hook(Box::new(|_| {}));
-        Immediate::encode(-100000000);
+        let error = Immediate::encode(-100000000).unwrap_err();
+        assert_eq!(error, ImmediateOutOfRange { value: -100000000 });
     }
 }
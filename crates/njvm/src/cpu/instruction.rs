@@ -1,5 +1,6 @@
 use crate::cpu::immediate::{Decoding, Encoding, Immediate};
 use crate::cpu::opcode::Opcode;
+use crate::error::{ImmediateOutOfRange, Trap};
 use crate::memory::instruction_register::Bytecode;
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -16,8 +17,14 @@ impl Instruction {
     pub fn encode_instruction(
         opcode: Opcode,
         immediate: Immediate,
-    ) -> Bytecode {
-        Opcode::encode(opcode) | Immediate::encode(immediate)
+    ) -> Result<Bytecode, ImmediateOutOfRange> {
+        Ok(Opcode::encode(opcode) | Immediate::encode(immediate)?)
+    }
+
+    /// Decodes `bytecode`, which fails if its opcode is undefined.
+    pub fn decode(bytecode: Bytecode) -> Result<Self, Trap> {
+        let opcode = Opcode::try_from((bytecode >> 24) as u8)?;
+        Ok(Instruction::new(opcode, Immediate::decode(bytecode)))
     }
 }
 
@@ -49,8 +56,13 @@ mod tests {
 
     #[test]
     fn test_encode_instruction() {
-        assert_eq!(Instruction::encode_instruction(Pushc, 1), 0x01000001);
-        assert_eq!(Instruction::encode_instruction(Pushc, -1), 0x01ffffff);
+        assert_eq!(Instruction::encode_instruction(Pushc, 1), Ok(0x01000001));
+        assert_eq!(Instruction::encode_instruction(Pushc, -1), Ok(0x01ffffff));
+        let error = ImmediateOutOfRange { value: 10000000 };
+        assert_eq!(
+            Instruction::encode_instruction(Pushc, 10000000),
+            Err(error)
+        );
     }
 
     #[test]
@@ -1,5 +1,8 @@
+pub mod big_integer;
 pub mod debugger;
 pub mod immediate;
 pub mod instruction;
 pub mod opcode;
 pub mod processor;
+pub mod profile;
+pub mod verifier;
@@ -1,3 +1,4 @@
+use crate::error::Trap;
 use crate::memory::instruction_register::Bytecode;
 
 #[repr(u8)]
@@ -35,6 +36,17 @@ pub enum Opcode {
     Pushr = 29,
     Popr = 30,
     Dup = 31,
+    New = 32,
+    Getf = 33,
+    Putf = 34,
+    Newa = 35,
+    Getfa = 36,
+    Putfa = 37,
+    Getsz = 38,
+    Pushn = 39,
+    Refeq = 40,
+    Refne = 41,
+    Pushk = 42,
 }
 
 impl std::fmt::Display for Opcode {
@@ -44,11 +56,27 @@ impl std::fmt::Display for Opcode {
     }
 }
 
+/// Decodes the opcode of code that has been checked, like a loaded binary.
+///
+/// # Panics
+///
+/// Panics if the opcode is undefined, see [`Opcode::try_from`].
 impl From<Bytecode> for Opcode {
     fn from(value: Bytecode) -> Self {
+        let opcode = (value >> 24) as u8;
+        Opcode::try_from(opcode)
+            .unwrap_or_else(|_| panic!("Unknown opcode {opcode}"))
+    }
+}
+
+impl TryFrom<u8> for Opcode {
+    type Error = Trap;
+
+    /// Returns the opcode numbered `value`, which fails with
+    /// [`Trap::UnknownOpcode`] if no instruction has that number.
+    fn try_from(value: u8) -> Result<Self, Trap> {
         use Opcode::*;
-        let opcode = value >> 24;
-        match opcode {
+        Ok(match value {
             0 => Halt,
             1 => Pushc,
             2 => Add,
@@ -81,8 +109,19 @@ impl From<Bytecode> for Opcode {
             29 => Pushr,
             30 => Popr,
             31 => Dup,
-            _ => panic!("Unknown opcode"),
-        }
+            32 => New,
+            33 => Getf,
+            34 => Putf,
+            35 => Newa,
+            36 => Getfa,
+            37 => Putfa,
+            38 => Getsz,
+            39 => Pushn,
+            40 => Refeq,
+            41 => Refne,
+            42 => Pushk,
+            _ => return Err(Trap::UnknownOpcode { opcode: value }),
+        })
     }
 }
 
@@ -90,6 +129,43 @@ impl Opcode {
     pub fn encode(opcode: Opcode) -> Bytecode {
         (opcode as Bytecode) << 24
     }
+
+    /// Returns whether the immediate value of the opcode is the address of
+    /// an instruction.
+    pub fn is_branch(self) -> bool {
+        matches!(self, Opcode::Jmp | Opcode::Brf | Opcode::Brt | Opcode::Call)
+    }
+
+    /// Returns whether the instruction takes an immediate value in
+    /// assembler source.
+    pub fn has_immediate(self) -> bool {
+        use Opcode::*;
+        matches!(
+            self,
+            Pushc
+                | Pushg
+                | Popg
+                | Asf
+                | Pushl
+                | Popl
+                | Jmp
+                | Brf
+                | Brt
+                | Call
+                | Drop
+                | New
+                | Getf
+                | Putf
+                | Pushk
+        )
+    }
+
+    /// Returns the opcode whose mnemonic is `name`.
+    pub fn from_name(name: &str) -> Option<Opcode> {
+        (Opcode::Halt as u8..=Opcode::Pushk as u8)
+            .filter_map(|opcode| Opcode::try_from(opcode).ok())
+            .find(|opcode| opcode.to_string() == name)
+    }
 }
 
 #[cfg(test)]
@@ -131,6 +207,17 @@ mod tests {
         assert_eq!(Opcode::encode(Pushr), 0x1d000000);
         assert_eq!(Opcode::encode(Popr), 0x1e000000);
         assert_eq!(Opcode::encode(Dup), 0x1f000000);
+        assert_eq!(Opcode::encode(New), 0x20000000);
+        assert_eq!(Opcode::encode(Getf), 0x21000000);
+        assert_eq!(Opcode::encode(Putf), 0x22000000);
+        assert_eq!(Opcode::encode(Newa), 0x23000000);
+        assert_eq!(Opcode::encode(Getfa), 0x24000000);
+        assert_eq!(Opcode::encode(Putfa), 0x25000000);
+        assert_eq!(Opcode::encode(Getsz), 0x26000000);
+        assert_eq!(Opcode::encode(Pushn), 0x27000000);
+        assert_eq!(Opcode::encode(Refeq), 0x28000000);
+        assert_eq!(Opcode::encode(Refne), 0x29000000);
+        assert_eq!(Opcode::encode(Pushk), 0x2a000000);
     }
 
     #[test]
@@ -167,6 +254,17 @@ mod tests {
         assert_eq!(Opcode::from(0x1d000000), Pushr);
         assert_eq!(Opcode::from(0x1e000000), Popr);
         assert_eq!(Opcode::from(0x1f000000), Dup);
+        assert_eq!(Opcode::from(0x20000000), New);
+        assert_eq!(Opcode::from(0x21000000), Getf);
+        assert_eq!(Opcode::from(0x22000000), Putf);
+        assert_eq!(Opcode::from(0x23000000), Newa);
+        assert_eq!(Opcode::from(0x24000000), Getfa);
+        assert_eq!(Opcode::from(0x25000000), Putfa);
+        assert_eq!(Opcode::from(0x26000000), Getsz);
+        assert_eq!(Opcode::from(0x27000000), Pushn);
+        assert_eq!(Opcode::from(0x28000000), Refeq);
+        assert_eq!(Opcode::from(0x29000000), Refne);
+        assert_eq!(Opcode::from(0x2a000000), Pushk);
     }
 
     #[test]
@@ -205,6 +303,17 @@ mod tests {
             (Pushr, "pushr"),
             (Popr, "popr"),
             (Dup, "dup"),
+            (New, "new"),
+            (Getf, "getf"),
+            (Putf, "putf"),
+            (Newa, "newa"),
+            (Getfa, "getfa"),
+            (Putfa, "putfa"),
+            (Getsz, "getsz"),
+            (Pushn, "pushn"),
+            (Refeq, "refeq"),
+            (Refne, "refne"),
+            (Pushk, "pushk"),
         ];
 
         for (opcode, expected) in test_cases {
@@ -212,6 +321,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_branch() {
+        assert!(Jmp.is_branch());
+        assert!(Call.is_branch());
+        assert!(!Ret.is_branch());
+        assert!(!Pushc.is_branch());
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Opcode::from_name("pushc"), Some(Pushc));
+        assert_eq!(Opcode::from_name("refne"), Some(Refne));
+        assert_eq!(Opcode::from_name("pushk"), Some(Pushk));
+        assert_eq!(Opcode::from_name("Pushc"), None);
+        assert_eq!(Opcode::from_name("push"), None);
+        assert!(Putf.has_immediate());
+        assert!(!Putfa.has_immediate());
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(Opcode::try_from(0u8), Ok(Halt));
+        assert_eq!(Opcode::try_from(42u8), Ok(Pushk));
+        let expected = Trap::UnknownOpcode { opcode: 43 };
+        assert_eq!(Opcode::try_from(43u8), Err(expected));
+    }
+
     #[test]
     #[should_panic(expected = "Unknown opcode")]
     fn test_unknown_opcode() {
@@ -1,293 +1,376 @@
+use std::cmp::Ordering::{Equal, Greater, Less};
 use std::fmt::Debug;
 use std::io::{BufRead, Write};
 
 use crate::cpu::immediate::Immediate;
+use crate::cpu::opcode::Opcode;
+use crate::error::{Trap, VmError};
+use crate::memory::heap::ObjRef;
 use crate::NinjaVM;
 
 impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
-    pub fn halt(&self) {
-        self.io_borrow()
-            .write_stdout("Ninja Virtual Machine stopped\n");
+    pub fn halt(&mut self) -> Result<(), VmError> {
+        if self.heap.stats {
+            self.collect_garbage();
+        }
+        self.io.write_stdout("Ninja Virtual Machine stopped\n");
+        self.io.flush_stdout();
+        Ok(())
     }
 
-    pub fn pushc(&mut self, immediate: Immediate) {
-        self.stack.push(immediate);
+    pub fn pushc(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        self.push_integer(immediate)
     }
 
-    pub fn add(&mut self) {
-        let n2 = self.stack.pop();
-        let n1 = self.stack.pop();
-        self.stack.push(n1 + n2);
+    /// Pushes constant `index` of the constant pool.
+    pub fn pushk(&mut self, index: Immediate) -> Result<(), VmError> {
+        let constant = self
+            .constants
+            .get(index as usize)
+            .ok_or(Trap::InvalidConstant { index })?
+            .clone();
+        let object = self.new_big_integer(&constant)?;
+        self.stack.push_object(object)?;
+        Ok(())
     }
 
-    pub fn sub(&mut self) {
-        let n2 = self.stack.pop();
-        let n1 = self.stack.pop();
-        self.stack.push(n1 - n2);
+    pub fn add(&mut self) -> Result<(), VmError> {
+        self.arithmetic(bigint::bigAdd)
     }
 
-    pub fn mul(&mut self) {
-        let n2 = self.stack.pop();
-        let n1 = self.stack.pop();
-        self.stack.push(n1 * n2);
+    pub fn sub(&mut self) -> Result<(), VmError> {
+        self.arithmetic(bigint::bigSub)
     }
 
-    pub fn div(&mut self) {
-        let n2 = self.stack.pop();
-        let n1 = self.stack.pop();
-        if n2 == 0 {
-            self.io_borrow().fatal_error("Division by zero error\n");
-        }
-        self.stack.push(n1 / n2);
+    pub fn mul(&mut self) -> Result<(), VmError> {
+        self.arithmetic(bigint::bigMul)
     }
 
-    pub fn modulo(&mut self) {
-        let n2 = self.stack.pop();
-        let n1 = self.stack.pop();
-        if n2 == 0 {
-            self.io_borrow().fatal_error("Division by zero error\n");
-        }
-        self.stack.push(n1 % n2);
+    pub fn div(&mut self) -> Result<(), VmError> {
+        let (quotient, _) = self.division()?;
+        self.stack.push_object(quotient)?;
+        Ok(())
+    }
+
+    pub fn modulo(&mut self) -> Result<(), VmError> {
+        let (_, remainder) = self.division()?;
+        self.stack.push_object(remainder)?;
+        Ok(())
     }
 
-    pub fn rdint(&mut self) {
+    pub fn rdint(&mut self) -> Result<(), VmError> {
         let mut byte_buffer = [0];
 
         loop {
-            if let Ok(()) = self
-                .io_borrow()
-                .stdin_borrow_mut()
-                .read_exact(&mut byte_buffer)
-            {
-                match byte_buffer[0] {
-                    b'-' => break,
-                    b'0' => break,
-                    b'1' => break,
-                    b'2' => break,
-                    b'3' => break,
-                    b'4' => break,
-                    b'5' => break,
-                    b'6' => break,
-                    b'7' => break,
-                    b'8' => break,
-                    b'9' => break,
-                    b' ' => continue,
-                    _ => self
-                        .io_borrow()
-                        .fatal_error("Error: input is not an integer\n"),
-                }
-            } else {
-                self.io_borrow()
-                    .fatal_error("Error: could not read character\n")
+            self.read_byte(&mut byte_buffer)?;
+            match byte_buffer[0] {
+                b'-' | b'0'..=b'9' => break,
+                b' ' => continue,
+                _ => return Err(Trap::NotAnInteger.into()),
             }
         }
 
         let mut buffer = vec![byte_buffer[0]];
 
         loop {
-            if let Ok(()) = self
-                .io_borrow()
-                .stdin_borrow_mut()
-                .read_exact(&mut byte_buffer)
-            {
-                match byte_buffer[0] {
-                    b'0' => buffer.push(byte_buffer[0]),
-                    b'1' => buffer.push(byte_buffer[0]),
-                    b'2' => buffer.push(byte_buffer[0]),
-                    b'3' => buffer.push(byte_buffer[0]),
-                    b'4' => buffer.push(byte_buffer[0]),
-                    b'5' => buffer.push(byte_buffer[0]),
-                    b'6' => buffer.push(byte_buffer[0]),
-                    b'7' => buffer.push(byte_buffer[0]),
-                    b'8' => buffer.push(byte_buffer[0]),
-                    b'9' => buffer.push(byte_buffer[0]),
-                    _ => break,
-                }
-            } else {
-                self.io_borrow()
-                    .fatal_error("Error: could not read character\n")
+            self.read_byte(&mut byte_buffer)?;
+            match byte_buffer[0] {
+                b'0'..=b'9' => buffer.push(byte_buffer[0]),
+                _ => break,
             }
         }
 
-        let immediate = match String::from_utf8(buffer).unwrap().parse() {
-            Ok(immediate) => immediate,
-            Err(_) => {
-                self.io_borrow().fatal_error("Error: integer is too big\n")
-            }
-        };
-
-        self.stack.push(immediate)
+        let integer = self.parse_integer(&buffer)?;
+        self.stack.push_object(integer)?;
+        Ok(())
     }
 
-    pub fn wrint(&mut self) {
-        let value = self.stack.pop();
-        let output = format!("{value}");
-        self.io_borrow().write_stdout(&output);
+    pub fn wrint(&mut self) -> Result<(), VmError> {
+        let value = self.stack.pop_object()?;
+        self.write_integer(value)
     }
 
-    pub fn rdchr(&mut self) {
+    pub fn rdchr(&mut self) -> Result<(), VmError> {
         let mut byte_buffer = [0];
-
-        match self
-            .io_borrow()
-            .stdin_borrow_mut()
-            .read_exact(&mut byte_buffer)
-        {
-            Ok(_) => {}
-            Err(_) => self
-                .io_borrow()
-                .fatal_error("Error: could not read character\n"),
-        };
-
+        self.read_byte(&mut byte_buffer)?;
         let immediate = byte_buffer[0] as Immediate;
-
-        self.stack.push(immediate)
+        self.push_integer(immediate)
     }
 
-    pub fn wrchr(&mut self) {
-        let character = self.stack.pop() as u8 as char;
-        let output = format!("{character}");
-        self.io_borrow().write_stdout(&output);
+    pub fn wrchr(&mut self) -> Result<(), VmError> {
+        let character = self.pop_integer()? as u8 as char;
+        self.io.print(format_args!("{character}"));
+        Ok(())
     }
 
-    pub fn pushg(&mut self, immediate: Immediate) {
-        self.stack.push(self.sda.data[immediate as usize]);
+    pub fn pushg(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        let index = self.global_index(Opcode::Pushg, immediate)?;
+        self.stack.push(self.sda.data[index])
     }
 
-    pub fn popg(&mut self, immediate: Immediate) {
-        self.sda.data[immediate as usize] = self.stack.pop()
+    pub fn popg(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        let index = self.global_index(Opcode::Popg, immediate)?;
+        self.sda.data[index] = self.stack.pop()?;
+        Ok(())
     }
 
-    pub fn asf(&mut self, immediate: Immediate) {
-        self.stack.push(self.stack.fp as Immediate);
+    pub fn asf(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        self.stack.push_number(self.stack.fp as Immediate)?;
         self.stack.fp = self.stack.sp;
-        let mut stack_size = self.stack.data.len();
-        stack_size += immediate as usize;
-        self.stack.data.resize(stack_size, 0);
-        self.stack.sp += immediate as usize;
+        self.stack.grow(immediate as usize)
     }
 
-    pub fn rsf(&mut self) {
-        let fp = self.stack.fp;
-        let sp = self.stack.sp;
-        let stack_size = self.stack.data.len() - (sp - fp);
-        self.stack.data.resize(stack_size, 0);
-        self.stack.sp = self.stack.fp;
-        self.stack.fp = self.stack.pop() as usize;
+    pub fn rsf(&mut self) -> Result<(), VmError> {
+        self.stack.pop_frame()
     }
 
-    pub fn pushl(&mut self, immediate: Immediate) {
-        let fp = self.stack.fp as isize;
-        let index = (fp + immediate as isize) as usize;
-        self.stack.push(self.stack.data[index]);
+    pub fn pushl(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        let index = self.local_index(Opcode::Pushl, immediate)?;
+        self.stack.push(self.stack.data[index])
     }
 
-    pub fn popl(&mut self, immediate: Immediate) {
-        let fp = self.stack.fp as isize;
-        let sp = self.stack.sp as isize;
-        let fp_index = (fp + immediate as isize) as usize;
-        let sp_index = (sp - 1) as usize;
-        self.stack.data[fp_index] = self.stack.data[sp_index];
+    pub fn popl(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+        let index = self.local_index(Opcode::Popl, immediate)?;
+        self.stack.data[index] = value;
+        Ok(())
     }
 
-    pub fn eq(&mut self) {
-        let b = self.stack.pop();
-        let a = self.stack.pop();
-        let result = if a == b { 1 } else { 0 };
-        self.stack.push(result);
+    pub fn eq(&mut self) -> Result<(), VmError> {
+        let result = if self.comparison()? == Equal { 1 } else { 0 };
+        self.push_integer(result)
     }
 
-    pub fn ne(&mut self) {
-        let b = self.stack.pop();
-        let a = self.stack.pop();
-        let result = if a != b { 1 } else { 0 };
-        self.stack.push(result);
+    pub fn ne(&mut self) -> Result<(), VmError> {
+        let result = if self.comparison()? != Equal { 1 } else { 0 };
+        self.push_integer(result)
     }
 
-    pub fn lt(&mut self) {
-        let b = self.stack.pop();
-        let a = self.stack.pop();
-        let result = if a < b { 1 } else { 0 };
-        self.stack.push(result);
+    pub fn lt(&mut self) -> Result<(), VmError> {
+        let result = if self.comparison()? == Less { 1 } else { 0 };
+        self.push_integer(result)
     }
 
-    pub fn le(&mut self) {
-        let b = self.stack.pop();
-        let a = self.stack.pop();
-        let result = if a <= b { 1 } else { 0 };
-        self.stack.push(result);
+    pub fn le(&mut self) -> Result<(), VmError> {
+        let result = if self.comparison()? != Greater { 1 } else { 0 };
+        self.push_integer(result)
     }
 
-    pub fn gt(&mut self) {
-        let b = self.stack.pop();
-        let a = self.stack.pop();
-        let result = if a > b { 1 } else { 0 };
-        self.stack.push(result);
+    pub fn gt(&mut self) -> Result<(), VmError> {
+        let result = if self.comparison()? == Greater { 1 } else { 0 };
+        self.push_integer(result)
     }
 
-    pub fn ge(&mut self) {
-        let b = self.stack.pop();
-        let a = self.stack.pop();
-        let result = if a >= b { 1 } else { 0 };
-        self.stack.push(result);
+    pub fn ge(&mut self) -> Result<(), VmError> {
+        let result = if self.comparison()? != Less { 1 } else { 0 };
+        self.push_integer(result)
     }
 
-    pub fn jmp(&mut self, immediate: Immediate) {
+    pub fn jmp(&mut self, immediate: Immediate) -> Result<(), VmError> {
         self.ir.pc = immediate as usize;
+        Ok(())
     }
 
-    pub fn brf(&mut self, immediate: Immediate) {
-        if self.stack.pop() == 0 {
+    pub fn brf(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        if self.pop_integer()? == 0 {
             self.ir.pc = immediate as usize;
         }
+        Ok(())
     }
 
-    pub fn brt(&mut self, immediate: Immediate) {
-        if self.stack.pop() == 1 {
+    pub fn brt(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        if self.pop_integer()? == 1 {
             self.ir.pc = immediate as usize;
         }
+        Ok(())
     }
 
-    pub fn call(&mut self, immediate: Immediate) {
+    pub fn call(&mut self, immediate: Immediate) -> Result<(), VmError> {
         let ra = self.ir.pc as Immediate;
-        self.stack.push(ra);
+        self.stack.push_number(ra)?;
         self.ir.pc = immediate as usize;
+        Ok(())
     }
 
-    pub fn ret(&mut self) {
-        self.ir.pc = self.stack.pop() as usize;
+    pub fn ret(&mut self) -> Result<(), VmError> {
+        let address = self.stack.pop_number()?;
+        let count = self.ir.data.len();
+        match usize::try_from(address) {
+            Ok(pc) if pc < count => {
+                self.ir.pc = pc;
+                Ok(())
+            }
+            _ => Err(Trap::InvalidReturnAddress {
+                pc: self.address(),
+                address,
+                count,
+            }
+            .into()),
+        }
     }
 
-    pub fn drop(&mut self, immediate: Immediate) {
+    pub fn drop(&mut self, immediate: Immediate) -> Result<(), VmError> {
         for _ in 0..immediate {
-            self.stack.pop();
+            self.stack.pop()?;
         }
+        Ok(())
     }
 
-    pub fn pushr(&mut self) {
-        if let Some(rv) = self.rv {
-            self.stack.push(rv);
-            self.rv = None;
-        } else {
-            self.io_borrow()
-                .fatal_error("Error: no value in return value register\n")
+    pub fn pushr(&mut self) -> Result<(), VmError> {
+        let rv = self.rv.take().ok_or(Trap::NoReturnValue)?;
+        self.stack.push(rv)?;
+        Ok(())
+    }
+
+    pub fn popr(&mut self) -> Result<(), VmError> {
+        self.rv = Some(self.stack.pop()?);
+        Ok(())
+    }
+
+    pub fn dup(&mut self) -> Result<(), VmError> {
+        let dup = self.stack.pop()?;
+        self.stack.push(dup)?;
+        self.stack.push(dup)?;
+        Ok(())
+    }
+
+    pub fn new_object(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        if immediate < 0 {
+            return Err(Trap::NegativeFieldCount.into());
         }
+        let object = self.new_compound(immediate as usize)?;
+        self.stack.push_object(object)?;
+        Ok(())
     }
 
-    pub fn popr(&mut self) {
-        self.rv = Some(self.stack.pop());
+    pub fn getf(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        let object = self.stack.pop_object()?;
+        let value = self.heap.get_field(object, immediate)?;
+        self.stack.push(value)?;
+        Ok(())
     }
 
-    pub fn dup(&mut self) {
-        let dup = self.stack.pop();
-        self.stack.push(dup);
-        self.stack.push(dup);
+    pub fn putf(&mut self, immediate: Immediate) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+        let object = self.stack.pop_object()?;
+        self.heap.set_field(object, immediate, value)
+    }
+
+    pub fn newa(&mut self) -> Result<(), VmError> {
+        let elements = self.pop_integer()?;
+        if elements < 0 {
+            return Err(Trap::NegativeElementCount.into());
+        }
+        let array = self.new_compound(elements as usize)?;
+        self.stack.push_object(array)?;
+        Ok(())
+    }
+
+    pub fn getfa(&mut self) -> Result<(), VmError> {
+        let index = self.pop_integer()?;
+        let array = self.stack.pop_object()?;
+        let value = self.heap.get_field(array, index)?;
+        self.stack.push(value)?;
+        Ok(())
+    }
+
+    pub fn putfa(&mut self) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+        let index = self.pop_integer()?;
+        let array = self.stack.pop_object()?;
+        self.heap.set_field(array, index, value)
+    }
+
+    pub fn getsz(&mut self) -> Result<(), VmError> {
+        let object = self.stack.pop_object()?;
+        let size = if self.heap.is_compound(object)? {
+            self.heap.size_of(object)? as Immediate
+        } else {
+            -1
+        };
+        self.push_integer(size)
+    }
+
+    pub fn pushn(&mut self) -> Result<(), VmError> {
+        self.stack.push_object(ObjRef::NIL)?;
+        Ok(())
+    }
+
+    pub fn refeq(&mut self) -> Result<(), VmError> {
+        let b = self.stack.pop_object()?;
+        let a = self.stack.pop_object()?;
+        let result = if a == b { 1 } else { 0 };
+        self.push_integer(result)
+    }
+
+    pub fn refne(&mut self) -> Result<(), VmError> {
+        let b = self.stack.pop_object()?;
+        let a = self.stack.pop_object()?;
+        let result = if a != b { 1 } else { 0 };
+        self.push_integer(result)
+    }
+
+    fn read_byte(&mut self, buffer: &mut [u8; 1]) -> Result<(), VmError> {
+        self.io.flush_stdout();
+        self.io
+            .stdin()
+            .read_exact(buffer)
+            .map_err(|err| VmError::io("read character", err))
+    }
+
+    /// The address of the instruction being executed.
+    pub(crate) fn address(&self) -> usize {
+        self.ir.pc.saturating_sub(1)
+    }
+
+    /// Returns the index of global variable `immediate` if it exists.
+    fn global_index(
+        &self,
+        opcode: Opcode,
+        immediate: Immediate,
+    ) -> Result<usize, VmError> {
+        let count = self.sda.data.len();
+        match usize::try_from(immediate) {
+            Ok(index) if index < count => Ok(index),
+            _ => Err(Trap::GlobalOutOfRange {
+                opcode,
+                pc: self.address(),
+                index: immediate,
+                count,
+            }
+            .into()),
+        }
+    }
+
+    /// Returns the stack index of the slot at offset `immediate` from the
+    /// frame pointer if it lies between the stack bottom and sp.
+    fn local_index(
+        &self,
+        opcode: Opcode,
+        immediate: Immediate,
+    ) -> Result<usize, VmError> {
+        let fp = self.stack.fp;
+        let sp = self.stack.sp;
+        match fp.checked_add_signed(immediate as isize) {
+            Some(index) if index < sp => Ok(index),
+            _ => Err(Trap::LocalOutOfRange {
+                opcode,
+                pc: self.address(),
+                index: immediate,
+                fp,
+                sp,
+            }
+            .into()),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::cpu::instruction::Instruction;
     use crate::io::InputOutput;
+    use crate::memory::heap::DEFAULT_HEAP_MEMORY;
+    use crate::memory::stack::StackSlot;
 
     use super::*;
 
@@ -301,9 +384,26 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(2);
+        vm.pushc(2).unwrap();
         assert_eq!(vm.stack.sp, 1);
-        assert_eq!(vm.stack.data[0], 2);
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_pushk_without_constant() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let pushk = Instruction::encode_instruction(Opcode::Pushk, 0).unwrap();
+        let result = vm.execute_instruction(pushk);
+        let trap = Trap::InvalidConstant { index: 0 };
+        assert!(matches!(result, Err(VmError::Trap(t)) if t == trap));
+        assert_eq!(vm.stack.sp, 0);
     }
 
     #[test]
@@ -316,11 +416,11 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(-1);
-        vm.pushc(2);
-        vm.add();
+        vm.pushc(-1).unwrap();
+        vm.pushc(2).unwrap();
+        vm.add().unwrap();
         assert_eq!(vm.stack.sp, 1);
-        assert_eq!(vm.stack.data[0], 1);
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
     }
 
     #[test]
@@ -333,11 +433,11 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(1);
-        vm.pushc(2);
-        vm.sub();
+        vm.pushc(1).unwrap();
+        vm.pushc(2).unwrap();
+        vm.sub().unwrap();
         assert_eq!(vm.stack.sp, 1);
-        assert_eq!(vm.stack.data[0], -1);
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), -1);
     }
 
     #[test]
@@ -350,11 +450,11 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(-1);
-        vm.pushc(-2);
-        vm.mul();
+        vm.pushc(-1).unwrap();
+        vm.pushc(-2).unwrap();
+        vm.mul().unwrap();
         assert_eq!(vm.stack.sp, 1);
-        assert_eq!(vm.stack.data[0], 2);
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 2);
     }
 
     #[test]
@@ -367,21 +467,19 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(-7);
-        vm.pushc(-2);
-        vm.div();
+        vm.pushc(-7).unwrap();
+        vm.pushc(-2).unwrap();
+        vm.div().unwrap();
         assert_eq!(vm.stack.sp, 1);
-        assert_eq!(vm.stack.data[0], 3);
-        vm.pushc(-3);
-        vm.div();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 3);
+        vm.pushc(-3).unwrap();
+        vm.div().unwrap();
         assert_eq!(vm.stack.sp, 1);
-        assert_eq!(vm.stack.data[0], -1);
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), -1);
     }
 
     #[test]
-    #[should_panic(expected = "Division by zero error")]
     fn test_division_by_zero_should_fail() {
-        std::panic::set_hook(Box::new(|_| {}));
         let stdin = b"";
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
@@ -390,11 +488,12 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(-2);
-        vm.pushc(4);
-        vm.pushc(-4);
-        vm.add();
-        vm.div();
+        vm.pushc(-2).unwrap();
+        vm.pushc(4).unwrap();
+        vm.pushc(-4).unwrap();
+        vm.add().unwrap();
+        let result = vm.div();
+        assert!(matches!(result, Err(VmError::Trap(Trap::DivisionByZero))));
     }
 
     #[test]
@@ -407,17 +506,15 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(-9);
-        vm.pushc(4);
-        vm.modulo();
+        vm.pushc(-9).unwrap();
+        vm.pushc(4).unwrap();
+        vm.modulo().unwrap();
         assert_eq!(vm.stack.sp, 1);
-        assert_eq!(vm.stack.data[0], -1);
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), -1);
     }
 
     #[test]
-    #[should_panic(expected = "Division by zero error")]
     fn test_modulo_with_zero_should_fail() {
-        std::panic::set_hook(Box::new(|_| {}));
         let stdin = b"";
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
@@ -426,11 +523,12 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(-2);
-        vm.pushc(4);
-        vm.pushc(-4);
-        vm.add();
-        vm.modulo();
+        vm.pushc(-2).unwrap();
+        vm.pushc(4).unwrap();
+        vm.pushc(-4).unwrap();
+        vm.add().unwrap();
+        let result = vm.modulo();
+        assert!(matches!(result, Err(VmError::Trap(Trap::DivisionByZero))));
     }
 
     #[test]
@@ -443,18 +541,16 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.rdint();
-        assert_eq!(vm.stack.data[0], -123);
-        vm.rdint();
-        assert_eq!(vm.stack.data[1], 456);
-        vm.rdint();
-        assert_eq!(vm.stack.data[2], -789);
+        vm.rdint().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), -123);
+        vm.rdint().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[1]).unwrap(), 456);
+        vm.rdint().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[2]).unwrap(), -789);
     }
 
     #[test]
-    #[should_panic(expected = "Error: input is not an integer")]
     fn test_rdint_fails_not_an_integer() {
-        std::panic::set_hook(Box::new(|_| {}));
         let stdin = b" 123 s  456  789   ";
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
@@ -463,16 +559,15 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.rdint();
-        assert_eq!(vm.stack.data[0], 123);
-        vm.rdint();
+        vm.rdint().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 123);
+        let result = vm.rdint();
+        assert!(matches!(result, Err(VmError::Trap(Trap::NotAnInteger))));
     }
 
     #[test]
-    #[should_panic(expected = "Error: integer is too big")]
-    fn test_rdint_fails_too_big() {
-        std::panic::set_hook(Box::new(|_| {}));
-        let stdin = b" 12345 67892424234242   ";
+    fn test_rdint_wrint_big_integer() {
+        let stdin = b" -67892424234242424242424242 ";
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
         let mut vm = NinjaVM::new(InputOutput::new(
@@ -480,9 +575,28 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.rdint();
-        assert_eq!(vm.stack.data[0], 12345);
-        vm.rdint();
+        vm.rdint().unwrap();
+        vm.wrint().unwrap();
+        let output = String::from_utf8(stdout).expect("Not utf-8");
+        assert_eq!(output, String::from("-67892424234242424242424242"));
+    }
+
+    #[test]
+    fn test_mul_does_not_overflow() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.pushc(i32::MAX).unwrap();
+        vm.dup().unwrap();
+        vm.mul().unwrap();
+        vm.wrint().unwrap();
+        let output = String::from_utf8(stdout).expect("Not utf-8");
+        assert_eq!(output, String::from("4611686014132420609"));
     }
 
     #[test]
@@ -496,8 +610,8 @@ mod tests {
             &mut stderr,
         ));
         let immediate: Immediate = 42;
-        vm.pushc(immediate);
-        vm.wrint();
+        vm.pushc(immediate).unwrap();
+        vm.wrint().unwrap();
         let output = String::from_utf8(stdout).expect("Not utf-8");
         assert_eq!(output, String::from("42"));
     }
@@ -512,26 +626,45 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.rdchr();
-        assert_eq!(vm.stack.data[0], '1' as Immediate);
-        vm.rdchr();
-        assert_eq!(vm.stack.data[1], '2' as Immediate);
-        vm.rdchr();
-        assert_eq!(vm.stack.data[2], '3' as Immediate);
-        vm.rdchr();
-        assert_eq!(vm.stack.data[3], ' ' as Immediate);
-        vm.rdchr();
-        assert_eq!(vm.stack.data[4], '4' as Immediate);
-        vm.rdchr();
-        assert_eq!(vm.stack.data[5], '5' as Immediate);
-        vm.rdchr();
-        assert_eq!(vm.stack.data[6], '6' as Immediate);
+        vm.rdchr().unwrap();
+        assert_eq!(
+            vm.integer_value(vm.stack.data[0]).unwrap(),
+            '1' as Immediate
+        );
+        vm.rdchr().unwrap();
+        assert_eq!(
+            vm.integer_value(vm.stack.data[1]).unwrap(),
+            '2' as Immediate
+        );
+        vm.rdchr().unwrap();
+        assert_eq!(
+            vm.integer_value(vm.stack.data[2]).unwrap(),
+            '3' as Immediate
+        );
+        vm.rdchr().unwrap();
+        assert_eq!(
+            vm.integer_value(vm.stack.data[3]).unwrap(),
+            ' ' as Immediate
+        );
+        vm.rdchr().unwrap();
+        assert_eq!(
+            vm.integer_value(vm.stack.data[4]).unwrap(),
+            '4' as Immediate
+        );
+        vm.rdchr().unwrap();
+        assert_eq!(
+            vm.integer_value(vm.stack.data[5]).unwrap(),
+            '5' as Immediate
+        );
+        vm.rdchr().unwrap();
+        assert_eq!(
+            vm.integer_value(vm.stack.data[6]).unwrap(),
+            '6' as Immediate
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Error: could not read character")]
     fn test_rdchr_fails() {
-        std::panic::set_hook(Box::new(|_| {}));
         let stdin = b"";
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
@@ -540,7 +673,8 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.rdchr();
+        let result = vm.rdchr();
+        assert!(matches!(result, Err(VmError::Io { .. })));
     }
 
     #[test]
@@ -554,8 +688,8 @@ mod tests {
             &mut stderr,
         ));
         let immediate: Immediate = '1'.to_ascii_lowercase() as i32;
-        vm.pushc(immediate);
-        vm.wrchr();
+        vm.pushc(immediate).unwrap();
+        vm.wrchr().unwrap();
         let output = String::from_utf8(stdout).expect("Not utf-8");
         assert_eq!(output, String::from("1"));
     }
@@ -567,10 +701,10 @@ mod tests {
         let mut stderr = Vec::new();
         let io = InputOutput::new(&stdin[..], &mut stdout, &mut stderr);
         let mut vm = NinjaVM::new(io);
-        vm.sda.data.resize(1, 0);
-        let value = 2;
+        vm.sda.data.resize(1, StackSlot::default());
+        let value = StackSlot::Number(2);
         vm.sda.data[0] = value;
-        vm.pushg(0);
+        vm.pushg(0).unwrap();
         assert_eq!(vm.sda.data[0], value);
     }
 
@@ -581,13 +715,38 @@ mod tests {
         let mut stderr = Vec::new();
         let io = InputOutput::new(&stdin[..], &mut stdout, &mut stderr);
         let mut vm = NinjaVM::new(io);
-        vm.sda.data.resize(1, 0);
-        let value = 2;
-        vm.stack.push(value);
-        vm.popg(0);
+        vm.sda.data.resize(1, StackSlot::default());
+        let value = StackSlot::Number(2);
+        vm.stack.push(value).unwrap();
+        vm.popg(0).unwrap();
         assert_eq!(vm.sda.data[0], value);
     }
 
+    #[test]
+    fn test_global_out_of_range() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let io = InputOutput::new(&stdin[..], &mut stdout, &mut stderr);
+        let mut vm = NinjaVM::new(io);
+        vm.sda.data.resize(2, StackSlot::default());
+        vm.ir.pc = 4;
+        let result = vm.pushg(2);
+        let expected = Trap::GlobalOutOfRange {
+            opcode: Opcode::Pushg,
+            pc: 3,
+            index: 2,
+            count: 2,
+        };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
+        vm.pushc(1).unwrap();
+        let result = vm.popg(-1);
+        assert!(matches!(
+            result,
+            Err(VmError::Trap(Trap::GlobalOutOfRange { index: -1, .. }))
+        ));
+    }
+
     #[test]
     fn test_asf() {
         let stdin = b"";
@@ -600,14 +759,33 @@ mod tests {
         ));
         let immediate = 100 as Immediate;
         let sp = vm.stack.sp;
-        vm.asf(immediate);
+        vm.asf(immediate).unwrap();
         assert_eq!(vm.stack.sp, (immediate + 1) as usize);
         assert_eq!(vm.stack.fp, sp + 1);
-        for i in 0..immediate as usize {
-            assert_eq!(vm.stack.data[i], 0)
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
+        for i in 1..=immediate as usize {
+            assert_eq!(vm.stack.data[i], StackSlot::default())
         }
     }
 
+    #[test]
+    fn test_asf_overflow() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::with_memory(
+            InputOutput::new(&stdin[..], &mut stdout, &mut stderr),
+            1,
+            DEFAULT_HEAP_MEMORY,
+        )
+        .unwrap();
+        vm.asf(63).unwrap();
+        vm.rsf().unwrap();
+        let result = vm.asf(64);
+        let expected = Trap::StackOverflow { sp: 1, fp: 1 };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
+    }
+
     #[test]
     fn test_rsf() {
         let stdin = b"";
@@ -619,13 +797,41 @@ mod tests {
             &mut stderr,
         ));
         let immediate = 100 as Immediate;
-        vm.asf(immediate);
-        vm.rsf();
+        vm.asf(immediate).unwrap();
+        vm.rsf().unwrap();
         assert_eq!(vm.stack.sp, 0);
         assert_eq!(vm.stack.fp, 0);
         assert_eq!(vm.stack.data.len(), 0);
     }
 
+    #[test]
+    fn test_rsf_below_frame() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.asf(2).unwrap();
+        let result = vm.drop(3);
+        let expected = Trap::FrameUnderflow { sp: 1, fp: 1 };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
+        vm.stack.sp = 0;
+        let result = vm.rsf();
+        let expected = Trap::FrameUnderflow { sp: 0, fp: 1 };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
+        vm.stack.sp = 1;
+        vm.stack.data[0] = StackSlot::Number(-5);
+        let result = vm.rsf();
+        let expected = Trap::InvalidFramePointer { fp: -5, sp: 0 };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
+        vm.stack.data[0] = StackSlot::Number(0);
+        vm.rsf().unwrap();
+        assert_eq!((vm.stack.sp, vm.stack.fp), (0, 0));
+    }
+
     #[test]
     fn test_pushl() {
         let stdin = b"";
@@ -637,14 +843,17 @@ mod tests {
             &mut stderr,
         ));
         let value_of_local_var = 10;
-        let nth_local_var = 2;
-        vm.asf(2);
-        vm.pushc(value_of_local_var);
-        vm.popl(nth_local_var);
+        let nth_local_var = 1;
+        vm.asf(2).unwrap();
+        vm.pushc(value_of_local_var).unwrap();
+        vm.popl(nth_local_var).unwrap();
         let sp = vm.stack.sp;
-        vm.pushl(nth_local_var);
+        vm.pushl(nth_local_var).unwrap();
         assert_eq!(vm.stack.sp, sp + 1);
-        assert_eq!(vm.stack.data[vm.stack.sp - 1], value_of_local_var);
+        assert_eq!(
+            vm.integer_value(vm.stack.data[vm.stack.sp - 1]).unwrap(),
+            value_of_local_var
+        );
     }
 
     #[test]
@@ -659,15 +868,55 @@ mod tests {
         ));
         let value_of_local_var = 10;
         let nth_local_var: usize = 0;
-        vm.asf(2);
-        vm.pushc(value_of_local_var);
-        vm.popl(nth_local_var as i32);
+        vm.asf(2).unwrap();
+        vm.pushc(value_of_local_var).unwrap();
+        vm.popl(nth_local_var as i32).unwrap();
+        assert_eq!(vm.stack.sp, vm.stack.fp + 2);
         assert_eq!(
-            vm.stack.data[vm.stack.fp + nth_local_var],
+            vm.integer_value(vm.stack.data[vm.stack.fp + nth_local_var])
+                .unwrap(),
             value_of_local_var
         );
     }
 
+    #[test]
+    fn test_local_out_of_range() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.pushc(1).unwrap();
+        vm.asf(2).unwrap();
+        let result = vm.pushl(-3);
+        let expected = Trap::LocalOutOfRange {
+            opcode: Opcode::Pushl,
+            pc: 0,
+            index: -3,
+            fp: 2,
+            sp: 4,
+        };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
+        let result = vm.pushl(2);
+        assert!(matches!(
+            result,
+            Err(VmError::Trap(Trap::LocalOutOfRange { index: 2, .. }))
+        ));
+        vm.pushc(1).unwrap();
+        let result = vm.popl(2);
+        assert!(matches!(
+            result,
+            Err(VmError::Trap(Trap::LocalOutOfRange {
+                opcode: Opcode::Popl,
+                ..
+            }))
+        ));
+        assert!(vm.pushl(-2).is_ok());
+    }
+
     #[test]
     fn test_eq() {
         let stdin = b"";
@@ -678,16 +927,16 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(1);
-        vm.pushc(2);
-        vm.eq();
-        assert_eq!(vm.stack.data[0], 0);
-        vm.pushc(-1);
-        vm.eq();
-        assert_eq!(vm.stack.data[0], 0);
-        vm.pushc(0);
-        vm.eq();
-        assert_eq!(vm.stack.data[0], 1);
+        vm.pushc(1).unwrap();
+        vm.pushc(2).unwrap();
+        vm.eq().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
+        vm.pushc(-1).unwrap();
+        vm.eq().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
+        vm.pushc(0).unwrap();
+        vm.eq().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
     }
 
     #[test]
@@ -700,16 +949,16 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(1);
-        vm.pushc(2);
-        vm.ne();
-        assert_eq!(vm.stack.data[0], 1);
-        vm.pushc(-1);
-        vm.ne();
-        assert_eq!(vm.stack.data[0], 1);
-        vm.pushc(1);
-        vm.ne();
-        assert_eq!(vm.stack.data[0], 0);
+        vm.pushc(1).unwrap();
+        vm.pushc(2).unwrap();
+        vm.ne().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
+        vm.pushc(-1).unwrap();
+        vm.ne().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
+        vm.pushc(1).unwrap();
+        vm.ne().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
     }
 
     #[test]
@@ -722,16 +971,16 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(1);
-        vm.pushc(2);
-        vm.lt();
-        assert_eq!(vm.stack.data[0], 1);
-        vm.pushc(0);
-        vm.lt();
-        assert_eq!(vm.stack.data[0], 0);
-        vm.pushc(1);
-        vm.lt();
-        assert_eq!(vm.stack.data[0], 1);
+        vm.pushc(1).unwrap();
+        vm.pushc(2).unwrap();
+        vm.lt().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
+        vm.pushc(0).unwrap();
+        vm.lt().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
+        vm.pushc(1).unwrap();
+        vm.lt().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
     }
 
     #[test]
@@ -744,16 +993,16 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(1);
-        vm.pushc(2);
-        vm.le();
-        assert_eq!(vm.stack.data[0], 1);
-        vm.pushc(1);
-        vm.le();
-        assert_eq!(vm.stack.data[0], 1);
-        vm.pushc(0);
-        vm.le();
-        assert_eq!(vm.stack.data[0], 0);
+        vm.pushc(1).unwrap();
+        vm.pushc(2).unwrap();
+        vm.le().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
+        vm.pushc(1).unwrap();
+        vm.le().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
+        vm.pushc(0).unwrap();
+        vm.le().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
     }
 
     #[test]
@@ -766,16 +1015,16 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(1);
-        vm.pushc(2);
-        vm.gt();
-        assert_eq!(vm.stack.data[0], 0);
-        vm.pushc(0);
-        vm.gt();
-        assert_eq!(vm.stack.data[0], 0);
-        vm.pushc(-1);
-        vm.gt();
-        assert_eq!(vm.stack.data[0], 1);
+        vm.pushc(1).unwrap();
+        vm.pushc(2).unwrap();
+        vm.gt().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
+        vm.pushc(0).unwrap();
+        vm.gt().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
+        vm.pushc(-1).unwrap();
+        vm.gt().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
     }
 
     #[test]
@@ -788,13 +1037,13 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushc(1);
-        vm.pushc(2);
-        vm.ge();
-        assert_eq!(vm.stack.data[0], 0);
-        vm.pushc(0);
-        vm.ge();
-        assert_eq!(vm.stack.data[0], 1);
+        vm.pushc(1).unwrap();
+        vm.pushc(2).unwrap();
+        vm.ge().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
+        vm.pushc(0).unwrap();
+        vm.ge().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
     }
 
     #[test]
@@ -808,7 +1057,7 @@ mod tests {
             &mut stderr,
         ));
         let immediate = 5;
-        vm.jmp(immediate);
+        vm.jmp(immediate).unwrap();
         assert_eq!(vm.ir.pc, immediate as usize);
     }
 
@@ -823,11 +1072,11 @@ mod tests {
             &mut stderr,
         ));
         let immediate = 5;
-        vm.pushc(1);
-        vm.brf(immediate);
+        vm.pushc(1).unwrap();
+        vm.brf(immediate).unwrap();
         assert_eq!(vm.ir.pc, 0);
-        vm.pushc(0);
-        vm.brf(immediate);
+        vm.pushc(0).unwrap();
+        vm.brf(immediate).unwrap();
         assert_eq!(vm.ir.pc, immediate as usize);
     }
 
@@ -842,11 +1091,11 @@ mod tests {
             &mut stderr,
         ));
         let immediate = 5;
-        vm.pushc(0);
-        vm.brt(immediate);
+        vm.pushc(0).unwrap();
+        vm.brt(immediate).unwrap();
         assert_eq!(vm.ir.pc, 0);
-        vm.pushc(1);
-        vm.brt(immediate);
+        vm.pushc(1).unwrap();
+        vm.brt(immediate).unwrap();
         assert_eq!(vm.ir.pc, immediate as usize);
     }
 
@@ -860,13 +1109,16 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.load("assets/a4/prog01.bin");
+        vm.load("assets/a4/prog01.bin").unwrap();
         vm.init();
         let immediate = 5;
         let ra = vm.ir.pc;
-        vm.call(immediate);
+        vm.call(immediate).unwrap();
         assert_eq!(vm.ir.pc, immediate as usize);
-        assert_eq!(vm.stack.data[vm.stack.sp - 1], ra as i32);
+        assert_eq!(
+            vm.integer_value(vm.stack.data[vm.stack.sp - 1]).unwrap(),
+            ra as i32
+        );
     }
 
     #[test]
@@ -879,21 +1131,48 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.load("assets/a4/prog01.bin");
+        vm.load("assets/a4/prog01.bin").unwrap();
         vm.init();
         let immediate = 5;
         let ra = vm.ir.pc;
-        vm.pushc(2);
-        vm.call(immediate);
-        assert_eq!(vm.stack.data[1], ra as i32);
+        vm.pushc(2).unwrap();
+        vm.call(immediate).unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[1]).unwrap(), ra as i32);
         assert_eq!(vm.ir.pc, immediate as usize);
         assert_ne!(vm.ir.pc, ra);
-        vm.ret();
-        assert_eq!(vm.stack.data[0], 2);
+        vm.ret().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 2);
         assert_eq!(vm.stack.data.len(), 1);
         assert_eq!(vm.ir.pc, ra)
     }
 
+    #[test]
+    fn test_ret_outside_program() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.load("assets/a4/prog01.bin").unwrap();
+        vm.init();
+        let count = vm.ir.data.len();
+        for address in [-1, count as Immediate] {
+            vm.stack.push_number(address).unwrap();
+            let result = vm.ret();
+            let expected = Trap::InvalidReturnAddress {
+                pc: 0,
+                address,
+                count,
+            };
+            assert!(
+                matches!(result, Err(VmError::Trap(trap)) if trap == expected)
+            );
+        }
+    }
+
     #[test]
     fn test_drop() {
         let stdin = b"";
@@ -904,15 +1183,15 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.load("assets/a4/prog01.bin");
+        vm.load("assets/a4/prog01.bin").unwrap();
         vm.init();
         let args = 10;
         for i in 0..args {
-            vm.pushc(i);
+            vm.pushc(i).unwrap();
         }
         assert_eq!(vm.stack.sp, args as usize);
         assert_eq!(vm.stack.data.len(), args as usize);
-        vm.drop(args);
+        vm.drop(args).unwrap();
         assert_eq!(vm.stack.sp, 0);
         assert_eq!(vm.stack.data.len(), 0);
     }
@@ -927,20 +1206,18 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        let rv = 5;
+        let rv = StackSlot::Number(5);
         let sp = vm.stack.sp;
         let len = vm.stack.data.len();
         vm.rv = Some(rv);
-        vm.pushr();
+        vm.pushr().unwrap();
         assert_eq!(vm.stack.sp, sp + 1);
         assert_eq!(vm.stack.data.len(), len + 1);
         assert_eq!(vm.rv, None);
     }
 
     #[test]
-    #[should_panic(expected = "Error: no value in return value register")]
     fn test_pushr_fails() {
-        std::panic::set_hook(Box::new(|_| {}));
         let stdin = b"";
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
@@ -949,7 +1226,8 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.pushr();
+        let result = vm.pushr();
+        assert!(matches!(result, Err(VmError::Trap(Trap::NoReturnValue))));
     }
 
     #[test]
@@ -963,19 +1241,17 @@ mod tests {
             &mut stderr,
         ));
         let rv = 5;
-        vm.pushc(rv);
+        vm.pushc(rv).unwrap();
         let len = vm.stack.data.len();
         assert_eq!(vm.rv, None);
-        assert_eq!(vm.stack.data[len - 1], rv);
-        vm.popr();
+        assert_eq!(vm.integer_value(vm.stack.data[len - 1]).unwrap(), rv);
+        vm.popr().unwrap();
         assert_eq!(vm.stack.data.len(), 0);
-        assert_eq!(vm.rv, Some(rv));
+        assert_eq!(vm.integer_value(vm.rv.unwrap()).unwrap(), rv);
     }
 
     #[test]
-    #[should_panic(expected = "Stack underflow: popped from empty stack")]
     fn test_popr_fails() {
-        std::panic::set_hook(Box::new(|_| {}));
         let stdin = b"";
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
@@ -984,7 +1260,8 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.popr();
+        let result = vm.popr();
+        assert!(matches!(result, Err(VmError::Trap(Trap::StackUnderflow))));
     }
 
     #[test]
@@ -999,18 +1276,22 @@ mod tests {
             &mut stderr,
         ));
         let immediate = 5;
-        vm.pushc(immediate);
+        vm.pushc(immediate).unwrap();
         let len = vm.stack.data.len();
-        assert_eq!(vm.stack.data[len - 1], immediate);
-        vm.dup();
-        assert_eq!(vm.stack.data[len - 1], immediate);
-        assert_eq!(vm.stack.data[len], immediate);
+        assert_eq!(
+            vm.integer_value(vm.stack.data[len - 1]).unwrap(),
+            immediate
+        );
+        vm.dup().unwrap();
+        assert_eq!(
+            vm.integer_value(vm.stack.data[len - 1]).unwrap(),
+            immediate
+        );
+        assert_eq!(vm.integer_value(vm.stack.data[len]).unwrap(), immediate);
     }
 
     #[test]
-    #[should_panic(expected = "Stack underflow: popped from empty stack")]
     fn test_dup_fails() {
-        std::panic::set_hook(Box::new(|_| {}));
         let stdin = b"";
         let mut stdout = Vec::new();
         let mut stderr = Vec::new();
@@ -1019,6 +1300,178 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        vm.dup();
+        let result = vm.dup();
+        assert!(matches!(result, Err(VmError::Trap(Trap::StackUnderflow))));
+    }
+
+    #[test]
+    fn test_new_object() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.new_object(2).unwrap();
+        assert_eq!(vm.stack.sp, 1);
+        let object = vm.stack.pop_object().unwrap();
+        assert!(vm.heap.is_compound(object).unwrap());
+        assert_eq!(vm.heap.size_of(object).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_getf_putf() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.new_object(2).unwrap();
+        vm.dup().unwrap();
+        vm.pushc(42).unwrap();
+        vm.putf(1).unwrap();
+        vm.dup().unwrap();
+        vm.getf(1).unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[1]).unwrap(), 42);
+        vm.drop(1).unwrap();
+        vm.getf(0).unwrap();
+        assert_eq!(vm.stack.data[0], StackSlot::Object(ObjRef::NIL));
+    }
+
+    #[test]
+    fn test_getf_out_of_bounds_fails() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.new_object(2).unwrap();
+        let result = vm.getf(2);
+        assert!(matches!(result, Err(VmError::Trap(Trap::IndexOutOfBounds))));
+    }
+
+    #[test]
+    fn test_putf_nil_fails() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.pushn().unwrap();
+        vm.pushc(1).unwrap();
+        let result = vm.putf(0);
+        assert!(matches!(result, Err(VmError::Trap(Trap::NilReference))));
+    }
+
+    #[test]
+    fn test_newa_getfa_putfa() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.pushc(3).unwrap();
+        vm.newa().unwrap();
+        vm.dup().unwrap();
+        vm.pushc(2).unwrap();
+        vm.pushc(7).unwrap();
+        vm.putfa().unwrap();
+        vm.dup().unwrap();
+        vm.pushc(2).unwrap();
+        vm.getfa().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[1]).unwrap(), 7);
+        vm.drop(1).unwrap();
+        vm.getsz().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_getfa_out_of_bounds_fails() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.pushc(3).unwrap();
+        vm.newa().unwrap();
+        vm.pushc(-1).unwrap();
+        let result = vm.getfa();
+        assert!(matches!(result, Err(VmError::Trap(Trap::IndexOutOfBounds))));
+    }
+
+    #[test]
+    fn test_newa_negative_fails() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.pushc(-1).unwrap();
+        let result = vm.newa();
+        assert!(matches!(
+            result,
+            Err(VmError::Trap(Trap::NegativeElementCount))
+        ));
+    }
+
+    #[test]
+    fn test_getsz_nil_fails() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.pushn().unwrap();
+        let result = vm.getsz();
+        assert!(matches!(result, Err(VmError::Trap(Trap::NilReference))));
+    }
+
+    #[test]
+    fn test_refeq_refne() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.new_object(1).unwrap();
+        vm.dup().unwrap();
+        vm.refeq().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
+        vm.drop(1).unwrap();
+        vm.new_object(1).unwrap();
+        vm.pushn().unwrap();
+        vm.refne().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
+        vm.drop(1).unwrap();
+        vm.pushn().unwrap();
+        vm.pushn().unwrap();
+        vm.refne().unwrap();
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 0);
     }
 }
@@ -0,0 +1,74 @@
+use std::ops::RangeInclusive;
+
+use crate::cpu::opcode::Opcode;
+use crate::memory::instruction_register::Bytecode;
+
+/// The instruction set of a Ninja binary version. Every version extends
+/// the instruction set of the previous one, so a profile is described by
+/// the last opcode it defines. Version 9 goes beyond [`crate::VERSION`]:
+/// it adds `pushk` and the constant pool, which the reference machine
+/// cannot load, so binaries only use it when they ask for it.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct VersionProfile {
+    version: u32,
+    last: Opcode,
+}
+
+impl VersionProfile {
+    pub const VERSIONS: RangeInclusive<u32> = 1..=9;
+
+    pub fn new(version: u32) -> Option<Self> {
+        use Opcode::*;
+        let last = match version {
+            1 => Wrchr,
+            2 => Popl,
+            3 => Brt,
+            4..=6 => Dup,
+            7 | 8 => Refne,
+            9 => Pushk,
+            _ => return None,
+        };
+        Some(Self { version, last })
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns whether the opcode of `bytecode` is defined by this version.
+    pub fn supports(&self, bytecode: Bytecode) -> bool {
+        bytecode >> 24 <= self.last as Bytecode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Opcode::*;
+
+    #[test]
+    fn test_known_versions() {
+        for version in VersionProfile::VERSIONS {
+            let profile = VersionProfile::new(version).unwrap();
+            assert_eq!(profile.version(), version);
+        }
+        assert_eq!(VersionProfile::new(0), None);
+        assert_eq!(VersionProfile::new(10), None);
+    }
+
+    #[test]
+    fn test_supports() {
+        let profile = VersionProfile::new(2).unwrap();
+        assert!(profile.supports(Opcode::encode(Popl) | 0xff));
+        assert!(!profile.supports(Opcode::encode(Eq)));
+        let profile = VersionProfile::new(4).unwrap();
+        assert!(profile.supports(Opcode::encode(Dup)));
+        assert!(!profile.supports(Opcode::encode(New)));
+        let profile = VersionProfile::new(8).unwrap();
+        assert!(profile.supports(Opcode::encode(Refne)));
+        assert!(!profile.supports(Opcode::encode(Pushk)));
+        let profile = VersionProfile::new(9).unwrap();
+        assert!(profile.supports(Opcode::encode(Pushk)));
+        assert!(!profile.supports(0x2b000000));
+    }
+}
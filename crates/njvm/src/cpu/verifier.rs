@@ -0,0 +1,309 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::io::{BufRead, Write};
+
+use crate::cpu::instruction::Instruction;
+use crate::cpu::opcode::Opcode;
+use crate::error::{VerifyError, VmError};
+use crate::memory::instruction_register::Bytecode;
+use crate::NinjaVM;
+
+/// The abstract stack of a procedure: the number of slots above the slot
+/// below its return address and the depths at which `asf` opened frames.
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct State {
+    depth: usize,
+    frames: Vec<usize>,
+}
+
+impl State {
+    /// The stack at the start of the program, which has no return address.
+    fn program() -> Self {
+        Self {
+            depth: 0,
+            frames: Vec::new(),
+        }
+    }
+
+    /// The stack at the start of a procedure, which holds its return
+    /// address.
+    fn procedure() -> Self {
+        Self {
+            depth: 1,
+            frames: Vec::new(),
+        }
+    }
+}
+
+/// Walks the control-flow graph of `code`, starting at address 0 and at the
+/// target of every reachable `call`, and checks that no instruction pops
+/// more values than its procedure pushed or into the saved frame pointer
+/// of an open frame, that every path reaches an
+/// instruction with the same stack depth, that `rsf` and `ret` match the
+/// frames and the return address of their procedure, that globals are
+/// below `global_count` and that no path runs past the last instruction.
+///
+/// Every opcode in `code` has to be defined, as after loading a binary.
+pub fn verify(
+    code: &[Bytecode],
+    global_count: usize,
+) -> Result<(), VerifyError> {
+    let mut states: Vec<Option<State>> = vec![None; code.len()];
+    let mut pending = VecDeque::new();
+    enter(&mut states, &mut pending, 0, 0, State::program())?;
+
+    while let Some(address) = pending.pop_front() {
+        let mut state =
+            states[address].clone().expect("pending states are set");
+        let Instruction { opcode, immediate } =
+            Instruction::from(code[address]);
+        let underflow = |depth| VerifyError::StackUnderflow {
+            opcode,
+            address,
+            depth,
+        };
+
+        use Opcode::*;
+        let (pops, pushes) = match opcode {
+            Halt => continue,
+            Pushc | Rdint | Rdchr | Pushl | Pushr | New | Pushn | Pushk => {
+                (0, 1)
+            }
+            Wrint | Wrchr | Popl | Popr | Brf | Brt => (1, 0),
+            Add | Sub | Mul | Div | Mod | Eq | Ne | Lt | Le | Gt | Ge
+            | Getfa | Refeq | Refne => (2, 1),
+            Getf | Newa | Getsz => (1, 1),
+            Putf => (2, 0),
+            Putfa => (3, 0),
+            Dup => (1, 2),
+            Drop => (immediate.max(0) as usize, 0),
+            Pushg | Popg => {
+                if !usize::try_from(immediate).is_ok_and(|i| i < global_count) {
+                    return Err(VerifyError::GlobalOutOfRange {
+                        opcode,
+                        address,
+                        index: immediate,
+                        count: global_count,
+                    });
+                }
+                if opcode == Pushg {
+                    (0, 1)
+                } else {
+                    (1, 0)
+                }
+            }
+            Asf => {
+                state.frames.push(state.depth);
+                (0, 1 + immediate.max(0) as usize)
+            }
+            Rsf => {
+                let base = state
+                    .frames
+                    .pop()
+                    .ok_or(VerifyError::NoFrame { address })?;
+                state.depth = base;
+                (0, 0)
+            }
+            Jmp | Call | Ret => (0, 0),
+        };
+        let floor = state.frames.last().map_or(0, |base| base + 1);
+        if pops > 0 && state.depth < floor + pops {
+            return Err(underflow(state.depth));
+        }
+        state.depth = state.depth - pops + pushes;
+
+        let next = address + 1;
+        match opcode {
+            Jmp => {
+                let target = immediate as usize;
+                enter(&mut states, &mut pending, address, target, state)?;
+            }
+            Brf | Brt => {
+                let target = immediate as usize;
+                enter(
+                    &mut states,
+                    &mut pending,
+                    address,
+                    target,
+                    state.clone(),
+                )?;
+                enter(&mut states, &mut pending, address, next, state)?;
+            }
+            Call => {
+                let target = immediate as usize;
+                enter(
+                    &mut states,
+                    &mut pending,
+                    address,
+                    target,
+                    State::procedure(),
+                )?;
+                enter(&mut states, &mut pending, address, next, state)?;
+            }
+            Ret => {
+                if state.depth == 0 {
+                    return Err(underflow(0));
+                }
+                if state.depth != 1 || !state.frames.is_empty() {
+                    return Err(VerifyError::UnbalancedReturn {
+                        address,
+                        depth: state.depth - 1,
+                    });
+                }
+            }
+            _ => enter(&mut states, &mut pending, address, next, state)?,
+        }
+    }
+    Ok(())
+}
+
+/// Records that the instruction at `from` continues at `to` with `state`
+/// and queues `to` unless it was reached with the same state before.
+fn enter(
+    states: &mut [Option<State>],
+    pending: &mut VecDeque<usize>,
+    from: usize,
+    to: usize,
+    state: State,
+) -> Result<(), VerifyError> {
+    let count = states.len();
+    let slot = match states.get_mut(to) {
+        Some(slot) => slot,
+        None if to == from + 1 || count == 0 => {
+            return Err(VerifyError::FallsOffEnd { address: from });
+        }
+        None => {
+            return Err(VerifyError::InvalidTarget {
+                address: from,
+                target: to,
+            });
+        }
+    };
+    match slot {
+        Some(known) if *known == state => Ok(()),
+        Some(known) => Err(VerifyError::DepthMismatch {
+            address: to,
+            expected: known.depth,
+            found: state.depth,
+        }),
+        None => {
+            *slot = Some(state);
+            pending.push_back(to);
+            Ok(())
+        }
+    }
+}
+
+impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
+    /// Verifies the loaded program, see [`verify`].
+    pub fn verify(&self) -> Result<(), VmError> {
+        verify(&self.ir.data, self.sda.data.len())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Opcode::*;
+
+    fn assemble(instructions: &[(Opcode, i32)]) -> Vec<Bytecode> {
+        instructions
+            .iter()
+            .map(|&(opcode, immediate)| {
+                Instruction::encode_instruction(opcode, immediate).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_procedures() {
+        let code = assemble(&[
+            (Pushc, 3),
+            (Call, 5),
+            (Drop, 1),
+            (Pushr, 0),
+            (Halt, 0),
+            (Asf, 1),
+            (Pushl, -3),
+            (Popl, 0),
+            (Pushl, 0),
+            (Brf, 11),
+            (Jmp, 13),
+            (Pushc, 1),
+            (Popl, 0),
+            (Pushl, 0),
+            (Popr, 0),
+            (Rsf, 0),
+            (Ret, 0),
+        ]);
+        assert_eq!(verify(&code, 0), Ok(()));
+    }
+
+    #[test]
+    fn test_underflow() {
+        let code = assemble(&[(Pushc, 1), (Add, 0), (Halt, 0)]);
+        let expected = VerifyError::StackUnderflow {
+            opcode: Add,
+            address: 1,
+            depth: 1,
+        };
+        assert_eq!(verify(&code, 0), Err(expected));
+        let code = assemble(&[(Asf, 2), (Drop, 3), (Rsf, 0), (Halt, 0)]);
+        let expected = VerifyError::StackUnderflow {
+            opcode: Drop,
+            address: 1,
+            depth: 3,
+        };
+        assert_eq!(verify(&code, 0), Err(expected));
+        let code = assemble(&[(Asf, 2), (Drop, 2), (Rsf, 0), (Halt, 0)]);
+        assert_eq!(verify(&code, 0), Ok(()));
+    }
+
+    #[test]
+    fn test_depth_mismatch() {
+        let code = assemble(&[(Pushc, 0), (Brt, 3), (Pushc, 1), (Halt, 0)]);
+        let expected = VerifyError::DepthMismatch {
+            address: 3,
+            expected: 0,
+            found: 1,
+        };
+        assert_eq!(verify(&code, 0), Err(expected));
+    }
+
+    #[test]
+    fn test_globals() {
+        let code = assemble(&[(Pushc, 1), (Popg, 1), (Halt, 0)]);
+        assert_eq!(verify(&code, 2), Ok(()));
+        let expected = VerifyError::GlobalOutOfRange {
+            opcode: Popg,
+            address: 1,
+            index: 1,
+            count: 1,
+        };
+        assert_eq!(verify(&code, 1), Err(expected));
+    }
+
+    #[test]
+    fn test_falls_off_end() {
+        let code = assemble(&[(Pushc, 1), (Wrint, 0)]);
+        let expected = VerifyError::FallsOffEnd { address: 1 };
+        assert_eq!(verify(&code, 0), Err(expected));
+        assert_eq!(
+            verify(&[], 0),
+            Err(VerifyError::FallsOffEnd { address: 0 })
+        );
+    }
+
+    #[test]
+    fn test_unbalanced_return() {
+        let code = assemble(&[(Call, 2), (Halt, 0), (Pushc, 1), (Ret, 0)]);
+        let expected = VerifyError::UnbalancedReturn {
+            address: 3,
+            depth: 1,
+        };
+        assert_eq!(verify(&code, 0), Err(expected));
+        let code = assemble(&[(Rsf, 0), (Halt, 0)]);
+        assert_eq!(verify(&code, 0), Err(VerifyError::NoFrame { address: 0 }));
+    }
+}
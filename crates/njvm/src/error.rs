@@ -0,0 +1,635 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+use std::io;
+
+use njvm_cli::UsageError;
+
+use crate::cpu::immediate::Immediate;
+use crate::cpu::opcode::Opcode;
+use crate::cpu::profile::VersionProfile;
+
+/// Everything that stops the virtual machine. Only the binary decides
+/// whether an error ends the process.
+#[derive(Debug)]
+pub enum VmError {
+    /// The command line arguments are invalid.
+    Usage(String),
+    /// The code file could not be loaded.
+    Load(LoadError),
+    /// The program performed an illegal operation.
+    Trap(Trap),
+    /// The program failed static verification.
+    Verify(VerifyError),
+    /// The assembler source could not be assembled.
+    Asm(AsmError),
+    /// The program did not halt within the step limit.
+    StepLimit { steps: u64 },
+    /// An error of the running program with the locations of the active
+    /// procedures, innermost first, taken from the symbol map.
+    Traced {
+        error: Box<VmError>,
+        trace: Vec<String>,
+    },
+    /// The stack or the heap could not be allocated.
+    OutOfMemory { memory: &'static str },
+    /// Reading the input of the program or the debugger failed.
+    Io {
+        action: &'static str,
+        source: io::Error,
+    },
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    NoCodeFile,
+    CannotOpen {
+        file: String,
+        source: io::Error,
+    },
+    InvalidBinary {
+        file: String,
+        source: HeaderError,
+    },
+    IllegalInstruction {
+        bytecode: u32,
+        address: usize,
+    },
+    InvalidTarget {
+        opcode: Opcode,
+        target: Immediate,
+        address: usize,
+        count: usize,
+    },
+    InvalidConstant {
+        index: Immediate,
+        address: usize,
+        count: usize,
+    },
+}
+
+/// A Ninja binary whose header or size is invalid.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum HeaderError {
+    BadMagic { found: [u8; 4] },
+    UnsupportedVersion { found: u32 },
+    Truncated { expected: usize, actual: usize },
+    TrailingBytes { count: usize },
+    InvalidConstantPool,
+    InvalidDebugInfo,
+}
+
+/// A program that can misuse the stack or its globals on some path.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum VerifyError {
+    StackUnderflow {
+        opcode: Opcode,
+        address: usize,
+        depth: usize,
+    },
+    DepthMismatch {
+        address: usize,
+        expected: usize,
+        found: usize,
+    },
+    GlobalOutOfRange {
+        opcode: Opcode,
+        address: usize,
+        index: Immediate,
+        count: usize,
+    },
+    InvalidTarget {
+        address: usize,
+        target: usize,
+    },
+    NoFrame {
+        address: usize,
+    },
+    UnbalancedReturn {
+        address: usize,
+        depth: usize,
+    },
+    FallsOffEnd {
+        address: usize,
+    },
+}
+
+/// Assembler source that cannot be translated, with the line of the
+/// offending statement.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum AsmError {
+    Syntax {
+        line: usize,
+    },
+    IllegalCharacter {
+        line: usize,
+        character: char,
+    },
+    ImmediateOutOfRange {
+        line: usize,
+        value: String,
+    },
+    UnsupportedVersion {
+        line: usize,
+        version: String,
+    },
+    UndefinedInstruction {
+        line: usize,
+        opcode: Opcode,
+        version: u32,
+    },
+    UndefinedSymbol {
+        name: String,
+    },
+    DuplicateSymbol {
+        name: String,
+    },
+    InvalidMap {
+        line: usize,
+    },
+}
+
+/// A value that does not fit into the 24 bit immediate of an instruction.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct ImmediateOutOfRange {
+    pub value: Immediate,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Trap {
+    StackOverflow {
+        sp: usize,
+        fp: usize,
+    },
+    StackUnderflow,
+    FrameUnderflow {
+        sp: usize,
+        fp: usize,
+    },
+    InvalidFramePointer {
+        fp: Immediate,
+        sp: usize,
+    },
+    GlobalOutOfRange {
+        opcode: Opcode,
+        pc: usize,
+        index: Immediate,
+        count: usize,
+    },
+    LocalOutOfRange {
+        opcode: Opcode,
+        pc: usize,
+        index: Immediate,
+        fp: usize,
+        sp: usize,
+    },
+    InvalidReturnAddress {
+        pc: usize,
+        address: Immediate,
+        count: usize,
+    },
+    PastEnd {
+        pc: usize,
+    },
+    InvalidConstant {
+        index: Immediate,
+    },
+    UnknownOpcode {
+        opcode: u8,
+    },
+    NotANumber,
+    NotAnObject,
+    DivisionByZero,
+    NotAnInteger,
+    NoReturnValue,
+    NegativeFieldCount,
+    NegativeElementCount,
+    NilReference,
+    NotCompound,
+    NotPrimitive,
+    IndexOutOfBounds,
+    HeapOverflow,
+    BigIntegerNilReference,
+    BigIntegerTooBig,
+}
+
+impl VmError {
+    pub fn io(action: &'static str, source: io::Error) -> Self {
+        VmError::Io { action, source }
+    }
+
+    /// The exit code of the process. Exceeding the step limit exits with
+    /// 124, like `timeout`, every other error with 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            VmError::StepLimit { .. } => 124,
+            VmError::Traced { error, .. } => error.exit_code(),
+            _ => 1,
+        }
+    }
+
+    pub fn unknown_argument(arg: &str) -> Self {
+        VmError::Usage(format!(
+            "Error: unknown option '{arg}', try './njvm --help'"
+        ))
+    }
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            VmError::Usage(message) => write!(f, "{message}"),
+            VmError::Load(error) => write!(f, "{error}"),
+            VmError::Trap(trap) => write!(f, "{trap}"),
+            VmError::Verify(error) => {
+                write!(f, "Error: verification failed: {error}")
+            }
+            VmError::Asm(error) => write!(f, "Error: {error}"),
+            VmError::StepLimit { steps } => {
+                write!(f, "Error: program did not halt within {steps} steps")
+            }
+            VmError::Traced { error, trace } => {
+                write!(f, "{error}")?;
+                for location in trace {
+                    write!(f, "\n    at {location}")?;
+                }
+                Ok(())
+            }
+            VmError::OutOfMemory { memory } => {
+                write!(f, "Error: cannot allocate {memory} memory")
+            }
+            VmError::Io { action, .. } => {
+                write!(f, "Error: could not {action}")
+            }
+        }
+    }
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            LoadError::NoCodeFile => write!(f, "Error: no code file specified"),
+            LoadError::CannotOpen { file, .. } => {
+                write!(f, "Error: cannot open code file '{file}'")
+            }
+            LoadError::InvalidBinary {
+                file,
+                source: HeaderError::BadMagic { .. },
+            } => write!(f, "Error: file '{file}' is not a Ninja binary"),
+            LoadError::InvalidBinary { file, source } => {
+                write!(f, "Error: code file '{file}' is invalid: {source}")
+            }
+            LoadError::IllegalInstruction { bytecode, address } => write!(
+                f,
+                "Error: illegal instruction 0x{bytecode:08X} at address {address}"
+            ),
+            LoadError::InvalidTarget {
+                opcode,
+                target,
+                address,
+                count,
+            } => write!(
+                f,
+                "Error: {opcode} {target} at address {address} jumps outside the program ({count} instructions)"
+            ),
+            LoadError::InvalidConstant {
+                index,
+                address,
+                count,
+            } => write!(
+                f,
+                "Error: pushk {index} at address {address} refers to no constant ({count} constants)"
+            ),
+        }
+    }
+}
+
+impl Display for HeaderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            HeaderError::BadMagic { found } => {
+                write!(f, "bad magic value {found:02x?}, expected \"NJBF\"")
+            }
+            HeaderError::UnsupportedVersion { found } => {
+                let last = VersionProfile::VERSIONS.end();
+                write!(
+                    f,
+                    "unsupported Ninja version {found}, expected 1 to {last}"
+                )
+            }
+            HeaderError::Truncated { expected, actual } => {
+                write!(f, "truncated to {actual} of {expected} bytes")
+            }
+            HeaderError::TrailingBytes { count } => {
+                write!(f, "{count} trailing bytes after the instructions")
+            }
+            HeaderError::InvalidConstantPool => {
+                write!(f, "invalid constant pool after the instructions")
+            }
+            HeaderError::InvalidDebugInfo => {
+                write!(f, "invalid debug information after the instructions")
+            }
+        }
+    }
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            VerifyError::StackUnderflow {
+                opcode,
+                address,
+                depth,
+            } => write!(
+                f,
+                "{opcode} at address {address} underflows the stack (depth = {depth})"
+            ),
+            VerifyError::DepthMismatch {
+                address,
+                expected,
+                found,
+            } => write!(
+                f,
+                "address {address} is reached with stack depths {expected} and {found}"
+            ),
+            VerifyError::GlobalOutOfRange {
+                opcode,
+                address,
+                index,
+                count,
+            } => write!(
+                f,
+                "{opcode} {index} at address {address} accesses a global beyond the {count} of the program"
+            ),
+            VerifyError::InvalidTarget { address, target } => write!(
+                f,
+                "instruction at address {address} continues at {target} outside the program"
+            ),
+            VerifyError::NoFrame { address } => {
+                write!(f, "rsf at address {address} has no stack frame to release")
+            }
+            VerifyError::UnbalancedReturn { address, depth } => write!(
+                f,
+                "ret at address {address} leaves {depth} values or a stack frame behind"
+            ),
+            VerifyError::FallsOffEnd { address } => write!(
+                f,
+                "execution continues past the last instruction after address {address}"
+            ),
+        }
+    }
+}
+
+impl Display for AsmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            AsmError::Syntax { line } => write!(f, "syntax error in line {line}"),
+            AsmError::IllegalCharacter { line, character } => write!(
+                f,
+                "line {line}, illegal character 0x{:02x}",
+                *character as u32
+            ),
+            AsmError::ImmediateOutOfRange { line, value } => {
+                write!(f, "line {line}, immediate value {value} out of range")
+            }
+            AsmError::UnsupportedVersion { line, version } => write!(
+                f,
+                "line {line}, unsupported Ninja version {version}, expected 1 to {}",
+                VersionProfile::VERSIONS.end()
+            ),
+            AsmError::UndefinedInstruction {
+                line,
+                opcode,
+                version,
+            } => write!(
+                f,
+                "line {line}, instruction {opcode} is not defined in version {version}"
+            ),
+            AsmError::UndefinedSymbol { name } => {
+                write!(f, "undefined symbol '{name}'")
+            }
+            AsmError::DuplicateSymbol { name } => {
+                write!(f, "symbol '{name}' defined more than once")
+            }
+            AsmError::InvalidMap { line } => {
+                write!(f, "invalid symbol map entry in line {line}")
+            }
+        }
+    }
+}
+
+impl Display for ImmediateOutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "immediate value {} out of range", self.value)
+    }
+}
+
+impl Display for Trap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let message = match self {
+            Trap::StackOverflow { sp, fp } => {
+                return write!(
+                    f,
+                    "Error: stack overflow (sp = {sp}, fp = {fp})"
+                );
+            }
+            Trap::FrameUnderflow { sp, fp } => {
+                return write!(
+                    f,
+                    "Error: stack underflow below the stack frame (sp = {sp}, fp = {fp})"
+                );
+            }
+            Trap::InvalidFramePointer { fp, sp } => {
+                return write!(
+                    f,
+                    "Error: rsf restores frame pointer {fp} outside the stack (sp = {sp})"
+                );
+            }
+            Trap::GlobalOutOfRange {
+                opcode,
+                pc,
+                index,
+                count,
+            } => {
+                return write!(
+                    f,
+                    "Error: {opcode} {index} at address {pc}: global index out of range (globals = {count})"
+                );
+            }
+            Trap::LocalOutOfRange {
+                opcode,
+                pc,
+                index,
+                fp,
+                sp,
+            } => {
+                let position = if (*fp as isize) + (*index as isize) < 0 {
+                    "below the stack bottom"
+                } else {
+                    "at or above sp"
+                };
+                return write!(
+                    f,
+                    "Error: {opcode} {index} at address {pc}: local slot lies {position} (fp = {fp}, sp = {sp})"
+                );
+            }
+            Trap::InvalidReturnAddress { pc, address, count } => {
+                return write!(
+                    f,
+                    "Error: ret at address {pc} returns to {address} outside the program ({count} instructions)"
+                );
+            }
+            Trap::PastEnd { pc } => {
+                return write!(
+                    f,
+                    "Error: execution ran past the last instruction to address {pc}"
+                );
+            }
+            Trap::InvalidConstant { index } => {
+                return write!(f, "Error: pushk {index} refers to no constant");
+            }
+            Trap::UnknownOpcode { opcode } => {
+                return write!(f, "Error: unknown opcode {opcode}");
+            }
+            Trap::StackUnderflow => "Stack underflow: popped from empty stack",
+            Trap::NotANumber => "Error: stack slot does not hold a number",
+            Trap::NotAnObject => {
+                "Error: stack slot does not hold an object reference"
+            }
+            Trap::DivisionByZero => "Division by zero error",
+            Trap::NotAnInteger => "Error: input is not an integer",
+            Trap::NoReturnValue => "Error: no value in return value register",
+            Trap::NegativeFieldCount => {
+                "Error: negative number of object fields"
+            }
+            Trap::NegativeElementCount => {
+                "Error: negative number of array elements"
+            }
+            Trap::NilReference => "Error: nil reference exception",
+            Trap::NotCompound => "Error: object is not a compound object",
+            Trap::NotPrimitive => "Error: object is not a primitive object",
+            Trap::IndexOutOfBounds => "Error: index out of bounds exception",
+            Trap::HeapOverflow => "Error: heap overflow",
+            Trap::BigIntegerNilReference => {
+                "Error: big integer library detected illegal nil reference"
+            }
+            Trap::BigIntegerTooBig => {
+                "Error: big integer too big for conversion to int"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+impl Error for VmError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            VmError::Load(error) => Some(error),
+            VmError::Verify(error) => Some(error),
+            VmError::Asm(error) => Some(error),
+            VmError::Traced { error, .. } => Some(error.as_ref()),
+            VmError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::CannotOpen { source, .. } => Some(source),
+            LoadError::InvalidBinary { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Error for HeaderError {}
+
+impl Error for VerifyError {}
+
+impl Error for AsmError {}
+
+impl Error for ImmediateOutOfRange {}
+
+impl Error for Trap {}
+
+impl From<LoadError> for VmError {
+    fn from(error: LoadError) -> Self {
+        VmError::Load(error)
+    }
+}
+
+impl From<UsageError> for VmError {
+    fn from(error: UsageError) -> Self {
+        VmError::Usage(error.to_string())
+    }
+}
+
+impl From<VerifyError> for VmError {
+    fn from(error: VerifyError) -> Self {
+        VmError::Verify(error)
+    }
+}
+
+impl From<AsmError> for VmError {
+    fn from(error: AsmError) -> Self {
+        VmError::Asm(error)
+    }
+}
+
+impl From<Trap> for VmError {
+    fn from(trap: Trap) -> Self {
+        VmError::Trap(trap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        let error = VmError::from(Trap::DivisionByZero);
+        assert_eq!(error.to_string(), "Division by zero error");
+        let error = VmError::from(LoadError::InvalidBinary {
+            file: String::from("prog.bin"),
+            source: HeaderError::TrailingBytes { count: 3 },
+        });
+        assert_eq!(
+            error.to_string(),
+            "Error: code file 'prog.bin' is invalid: 3 trailing bytes after the instructions"
+        );
+        let error = VmError::from(LoadError::InvalidBinary {
+            file: String::from("prog.bin"),
+            source: HeaderError::BadMagic { found: *b"NJBX" },
+        });
+        assert_eq!(
+            error.to_string(),
+            "Error: file 'prog.bin' is not a Ninja binary"
+        );
+        let source = io::Error::from(io::ErrorKind::UnexpectedEof);
+        let error = VmError::io("read character", source);
+        assert_eq!(error.to_string(), "Error: could not read character");
+        assert!(error.source().is_some());
+        let error = VmError::from(Trap::LocalOutOfRange {
+            opcode: Opcode::Pushl,
+            pc: 7,
+            index: -3,
+            fp: 2,
+            sp: 4,
+        });
+        assert_eq!(
+            error.to_string(),
+            "Error: pushl -3 at address 7: local slot lies below the stack bottom (fp = 2, sp = 4)"
+        );
+        let error = VmError::Traced {
+            error: Box::new(VmError::StepLimit { steps: 5 }),
+            trace: vec![String::from("_fib+3"), String::from("_main+7")],
+        };
+        assert_eq!(
+            error.to_string(),
+            "Error: program did not halt within 5 steps\n    at _fib+3\n    at _main+7"
+        );
+        assert_eq!(error.exit_code(), 124);
+    }
+}
@@ -0,0 +1,106 @@
+use bigint::BigInt;
+
+use crate::error::HeaderError;
+use crate::io::Reader;
+
+/// The optional section after the instructions of a Ninja binary with the
+/// numbers that `pushk` pushes, for numbers beyond the 24 bit immediate of
+/// `pushc`. The immediate of `pushk` is the index of its number.
+///
+/// It starts with `NJKP` followed by the number of constants as a little
+/// endian word and every constant in decimal, prefixed with the number of
+/// its digits and sign.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct ConstantPool {
+    constants: Vec<BigInt>,
+}
+
+impl ConstantPool {
+    pub const MAGIC: [u8; 4] = *b"NJKP";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the pool at the start of `bytes` and returns it together
+    /// with the bytes after it.
+    pub fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), HeaderError> {
+        const INVALID: HeaderError = HeaderError::InvalidConstantPool;
+        let mut reader = Reader {
+            bytes: bytes.strip_prefix(&Self::MAGIC).ok_or(INVALID)?,
+            error: INVALID,
+        };
+        let mut pool = Self::new();
+        for _ in 0..reader.word()? {
+            let constant = reader.name()?.parse().or(Err(INVALID))?;
+            pool.constants.push(constant);
+        }
+        Ok((pool, reader.bytes))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Self::MAGIC.to_vec();
+        let count = self.constants.len() as u32;
+        bytes.extend_from_slice(&count.to_le_bytes());
+        for constant in &self.constants {
+            let decimal = constant.to_string();
+            bytes.extend_from_slice(&(decimal.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(decimal.as_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the index of `constant`, adding it to the pool unless it
+    /// is already there.
+    pub fn insert(&mut self, constant: BigInt) -> usize {
+        match self.constants.iter().position(|c| *c == constant) {
+            Some(index) => index,
+            None => {
+                self.constants.push(constant);
+                self.constants.len() - 1
+            }
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&BigInt> {
+        self.constants.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.constants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constants.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut pool = ConstantPool::new();
+        let big: BigInt = "-123456789012345678901234567890".parse().unwrap();
+        assert_eq!(pool.insert(BigInt::from(10000000)), 0);
+        assert_eq!(pool.insert(big.clone()), 1);
+        assert_eq!(pool.insert(BigInt::from(10000000)), 0);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.get(1), Some(&big));
+        let mut bytes = pool.to_bytes();
+        assert_eq!(&bytes[..4], b"NJKP");
+        bytes.push(7);
+        assert_eq!(ConstantPool::parse(&bytes), Ok((pool, &[7][..])));
+    }
+
+    #[test]
+    fn test_invalid_pool() {
+        let invalid = Err(HeaderError::InvalidConstantPool);
+        let mut bytes = b"NJKP\x01\0\0\0\x02\0\0\0".to_vec();
+        assert_eq!(ConstantPool::parse(&bytes), invalid);
+        bytes.extend_from_slice(b"1x");
+        assert_eq!(ConstantPool::parse(&bytes), invalid);
+        assert_eq!(ConstantPool::parse(b"NJDI"), invalid);
+    }
+}
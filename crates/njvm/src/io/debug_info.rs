@@ -0,0 +1,197 @@
+use crate::asm::symbols::SymbolMap;
+use crate::cpu::immediate::Immediate;
+use crate::cpu::instruction::Instruction;
+use crate::cpu::opcode::Opcode;
+use crate::error::HeaderError;
+use crate::io::Reader;
+use crate::memory::instruction_register::Bytecode;
+
+/// The names of the local variables of a procedure by their offset from
+/// the frame pointer.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct Frame {
+    /// The address of the first instruction of the procedure.
+    pub address: usize,
+    pub locals: Vec<(Immediate, String)>,
+}
+
+/// The optional section after the instructions of a Ninja binary. The
+/// reference loaders ignore it like any bytes after the instructions, only
+/// [`Header::split`](crate::io::header::Header::split) rejects trailing
+/// bytes other than the known sections.
+///
+/// It starts with `NJDI` and holds four tables of little endian words:
+/// the labels as address and name, the global variables as index and name,
+/// the source line of every instruction, or none, and the frames as the
+/// address of their procedure, the number of locals and every local as
+/// offset and name. A table starts with the number of its entries and a
+/// name with the number of its UTF-8 bytes.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct DebugInfo {
+    pub symbols: SymbolMap,
+    pub globals: Vec<(u32, String)>,
+    pub lines: Vec<u32>,
+    pub frames: Vec<Frame>,
+}
+
+impl DebugInfo {
+    pub const MAGIC: [u8; 4] = *b"NJDI";
+
+    /// Parses the section `bytes` of a binary with `instruction_count`
+    /// instructions.
+    pub fn parse(
+        bytes: &[u8],
+        instruction_count: usize,
+    ) -> Result<Self, HeaderError> {
+        let mut reader = Reader {
+            bytes: bytes.strip_prefix(&Self::MAGIC).ok_or(INVALID)?,
+            error: INVALID,
+        };
+        let mut info = Self::default();
+        for _ in 0..reader.word()? {
+            let address = reader.word()? as usize;
+            info.symbols.insert(&reader.name()?, address);
+        }
+        for _ in 0..reader.word()? {
+            info.globals.push((reader.word()?, reader.name()?));
+        }
+        for _ in 0..reader.word()? {
+            info.lines.push(reader.word()?);
+        }
+        for _ in 0..reader.word()? {
+            let mut frame = Frame {
+                address: reader.word()? as usize,
+                locals: Vec::new(),
+            };
+            for _ in 0..reader.word()? {
+                let offset = reader.word()? as Immediate;
+                frame.locals.push((offset, reader.name()?));
+            }
+            info.frames.push(frame);
+        }
+        let lines = info.lines.len();
+        if !reader.bytes.is_empty()
+            || (lines != 0 && lines != instruction_count)
+        {
+            return Err(INVALID);
+        }
+        Ok(info)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Self::MAGIC.to_vec();
+        let word = |bytes: &mut Vec<u8>, word: usize| {
+            bytes.extend_from_slice(&(word as u32).to_le_bytes());
+        };
+        let name = |bytes: &mut Vec<u8>, name: &str| {
+            bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+        };
+        let symbols: Vec<_> = self.symbols.iter().collect();
+        word(&mut bytes, symbols.len());
+        for (symbol, address) in symbols {
+            word(&mut bytes, address);
+            name(&mut bytes, symbol);
+        }
+        word(&mut bytes, self.globals.len());
+        for (index, global) in &self.globals {
+            word(&mut bytes, *index as usize);
+            name(&mut bytes, global);
+        }
+        word(&mut bytes, self.lines.len());
+        for line in &self.lines {
+            word(&mut bytes, *line as usize);
+        }
+        word(&mut bytes, self.frames.len());
+        for frame in &self.frames {
+            word(&mut bytes, frame.address);
+            word(&mut bytes, frame.locals.len());
+            for (offset, local) in &frame.locals {
+                bytes.extend_from_slice(&offset.to_le_bytes());
+                name(&mut bytes, local);
+            }
+        }
+        bytes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Returns the name of global variable `index`.
+    pub fn global(&self, index: usize) -> Option<&str> {
+        self.globals
+            .iter()
+            .find(|(global, _)| *global as usize == index)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Returns the frame of the procedure that contains `address`.
+    pub fn frame(&self, code: &[Bytecode], address: usize) -> Option<&Frame> {
+        let procedure = procedure_start(code, address);
+        self.frames.iter().find(|frame| frame.address == procedure)
+    }
+}
+
+/// Returns the start of the procedure that contains `address`. Procedures
+/// start at address 0 and at the target of every `call`.
+pub fn procedure_start(code: &[Bytecode], address: usize) -> usize {
+    code.iter()
+        .map(|&bytecode| Instruction::from(bytecode))
+        .filter(|instruction| instruction.opcode == Opcode::Call)
+        .map(|instruction| instruction.immediate as usize)
+        .filter(|&target| target <= address)
+        .max()
+        .unwrap_or(0)
+}
+
+const INVALID: HeaderError = HeaderError::InvalidDebugInfo;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn debug_info() -> DebugInfo {
+        let mut info = DebugInfo::default();
+        info.symbols.insert("_main", 0);
+        info.symbols.insert("_fib", 2);
+        info.globals.push((1, String::from("count")));
+        info.lines = vec![3, 4, 7, 8];
+        info.frames.push(Frame {
+            address: 2,
+            locals: vec![(-3, String::from("n")), (0, String::from("i"))],
+        });
+        info
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let info = debug_info();
+        let bytes = info.to_bytes();
+        assert_eq!(&bytes[..4], b"NJDI");
+        assert_eq!(DebugInfo::parse(&bytes, 4), Ok(info));
+        assert_eq!(DebugInfo::parse(&bytes, 5), Err(INVALID));
+        assert_eq!(
+            DebugInfo::parse(&bytes[..bytes.len() - 1], 4),
+            Err(INVALID)
+        );
+        let mut bytes = bytes;
+        bytes.push(0);
+        assert_eq!(DebugInfo::parse(&bytes, 4), Err(INVALID));
+    }
+
+    #[test]
+    fn test_frame() {
+        let code = [
+            Instruction::encode_instruction(Opcode::Call, 2).unwrap(),
+            Instruction::encode_instruction(Opcode::Halt, 0).unwrap(),
+            Instruction::encode_instruction(Opcode::Asf, 1).unwrap(),
+            Instruction::encode_instruction(Opcode::Ret, 0).unwrap(),
+        ];
+        let info = debug_info();
+        assert_eq!(info.frame(&code, 1), None);
+        assert_eq!(info.frame(&code, 3).unwrap().locals[0].1, "n");
+        assert_eq!(info.global(1), Some("count"));
+        assert_eq!(info.global(0), None);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::cpu::profile::VersionProfile;
+use crate::error::HeaderError;
+use crate::io::constant_pool::ConstantPool;
+use crate::io::debug_info::DebugInfo;
+use crate::VERSION;
+
+/// The header of a Ninja binary. It is followed by `instruction_count`
+/// little endian instruction words and optionally by a [`ConstantPool`]
+/// and a [`DebugInfo`] section, in this order.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Header {
+    pub magic: [u8; 4],
+    pub version: u32,
+    pub instruction_count: u32,
+    pub global_count: u32,
+}
+
+impl Header {
+    pub const MAGIC: [u8; 4] = *b"NJBF";
+    pub const SIZE: usize = 16;
+
+    pub fn new(instruction_count: u32, global_count: u32) -> Self {
+        Self {
+            magic: Self::MAGIC,
+            version: VERSION,
+            instruction_count,
+            global_count,
+        }
+    }
+
+    /// Parses the header at the start of `bytes` and checks its magic value
+    /// and that its version has a profile.
+    pub fn parse(bytes: &[u8]) -> Result<Self, HeaderError> {
+        if bytes.len() >= 4 && !bytes.starts_with(&Self::MAGIC) {
+            let mut found = [0; 4];
+            found.copy_from_slice(&bytes[..4]);
+            return Err(HeaderError::BadMagic { found });
+        }
+        if bytes.len() < Self::SIZE {
+            return Err(HeaderError::Truncated {
+                expected: Self::SIZE,
+                actual: bytes.len(),
+            });
+        }
+        let word = |index: usize| {
+            let offset = index * 4;
+            let mut word = [0; 4];
+            word.copy_from_slice(&bytes[offset..offset + 4]);
+            u32::from_le_bytes(word)
+        };
+        let header = Self {
+            magic: Self::MAGIC,
+            version: word(1),
+            instruction_count: word(2),
+            global_count: word(3),
+        };
+        if VersionProfile::new(header.version).is_none() {
+            return Err(HeaderError::UnsupportedVersion {
+                found: header.version,
+            });
+        }
+        Ok(header)
+    }
+
+    /// Parses the header of a whole binary and splits off the instruction
+    /// words and the sections after them. Anything but these sections after
+    /// the instructions is an error.
+    pub fn split(file: &[u8]) -> Result<Binary<'_>, HeaderError> {
+        let header = Self::parse(file)?;
+        let expected = Self::SIZE + header.code_size();
+        if file.len() < expected {
+            let actual = file.len();
+            return Err(HeaderError::Truncated { expected, actual });
+        }
+        let (code, mut rest) = file[Self::SIZE..].split_at(header.code_size());
+        let mut constants = None;
+        if rest.starts_with(&ConstantPool::MAGIC) {
+            let (pool, after) = ConstantPool::parse(rest)?;
+            constants = Some(pool);
+            rest = after;
+        }
+        let debug_info = match rest {
+            [] => None,
+            rest if rest.starts_with(&DebugInfo::MAGIC) => {
+                let count = header.instruction_count as usize;
+                Some(DebugInfo::parse(rest, count)?)
+            }
+            rest => {
+                let count = rest.len();
+                return Err(HeaderError::TrailingBytes { count });
+            }
+        };
+        Ok(Binary {
+            header,
+            code,
+            constants,
+            debug_info,
+        })
+    }
+
+    /// The number of bytes of instruction words following the header.
+    pub fn code_size(&self) -> usize {
+        self.instruction_count as usize * 4
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[..4].copy_from_slice(&self.magic);
+        bytes[4..8].copy_from_slice(&self.version.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.instruction_count.to_le_bytes());
+        bytes[12..].copy_from_slice(&self.global_count.to_le_bytes());
+        bytes
+    }
+}
+
+/// A Ninja binary split into its header, its instruction words and its
+/// optional sections.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Binary<'a> {
+    pub header: Header,
+    pub code: &'a [u8],
+    pub constants: Option<ConstantPool>,
+    pub debug_info: Option<DebugInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary(header: Header, code: &[u8]) -> Vec<u8> {
+        let mut file = header.to_bytes().to_vec();
+        file.extend_from_slice(code);
+        file
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let header = Header::new(2, 3);
+        let file = binary(header, &[0; 8]);
+        assert_eq!(&file[..4], b"NJBF");
+        let parsed = Header::split(&file).unwrap();
+        assert_eq!(parsed.header, header);
+        assert_eq!(parsed.code.len(), 8);
+        assert_eq!(parsed.constants, None);
+        assert_eq!(parsed.debug_info, None);
+    }
+
+    #[test]
+    fn test_bad_magic() {
+        let mut file = binary(Header::new(0, 0), &[]);
+        file[0] = b'X';
+        let result = Header::parse(&file);
+        assert_eq!(result, Err(HeaderError::BadMagic { found: *b"XJBF" }));
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut header = Header::new(0, 0);
+        header.version = 0x0104;
+        let result = Header::parse(&header.to_bytes());
+        let expected = HeaderError::UnsupportedVersion { found: 0x0104 };
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_truncated() {
+        let file = binary(Header::new(2, 0), &[0; 6]);
+        let result = Header::split(&file);
+        let expected = HeaderError::Truncated {
+            expected: 24,
+            actual: 22,
+        };
+        assert_eq!(result, Err(expected));
+        let result = Header::parse(b"NJBF");
+        let expected = HeaderError::Truncated {
+            expected: 16,
+            actual: 4,
+        };
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn test_trailing_bytes() {
+        let file = binary(Header::new(1, 0), &[0; 5]);
+        let result = Header::split(&file);
+        assert_eq!(result, Err(HeaderError::TrailingBytes { count: 1 }));
+    }
+
+    #[test]
+    fn test_debug_info() {
+        let debug_info = DebugInfo {
+            lines: vec![1, 2],
+            ..DebugInfo::default()
+        };
+        let mut file = binary(Header::new(2, 0), &[0; 8]);
+        file.extend_from_slice(&debug_info.to_bytes());
+        let parsed = Header::split(&file).unwrap();
+        assert_eq!(parsed.code.len(), 8);
+        assert_eq!(parsed.debug_info, Some(debug_info));
+        file.pop();
+        let result = Header::split(&file);
+        assert_eq!(result, Err(HeaderError::InvalidDebugInfo));
+    }
+
+    #[test]
+    fn test_constant_pool() {
+        let mut pool = ConstantPool::new();
+        pool.insert(bigint::BigInt::from(10000000));
+        let mut file = binary(Header::new(1, 0), &[0; 4]);
+        file.extend_from_slice(&pool.to_bytes());
+        let parsed = Header::split(&file).unwrap();
+        assert_eq!(parsed.constants.as_ref(), Some(&pool));
+        assert_eq!(parsed.debug_info, None);
+        let debug_info = DebugInfo {
+            lines: vec![1],
+            ..DebugInfo::default()
+        };
+        file.extend_from_slice(&debug_info.to_bytes());
+        let parsed = Header::split(&file).unwrap();
+        assert_eq!(parsed.constants, Some(pool));
+        assert_eq!(parsed.debug_info, Some(debug_info));
+        let mut file = binary(Header::new(1, 0), &[0; 4]);
+        file.extend_from_slice(b"NJKP\x01\0\0\0");
+        let result = Header::split(&file);
+        assert_eq!(result, Err(HeaderError::InvalidConstantPool));
+    }
+}
@@ -1,22 +1,32 @@
-use std::fmt::Debug;
-use std::io::{StderrLock, StdinLock, StdoutLock, Write};
-use std::{cell::RefCell, io::BufRead, rc::Rc};
+pub mod constant_pool;
+pub mod debug_info;
+pub mod header;
 
-use crate::VERSION;
+use std::fmt::{self, Debug};
+use std::io::{BufRead, BufWriter, StderrLock, StdinLock, StdoutLock, Write};
 
+use crate::error::{HeaderError, LoadError, VmError};
+use crate::STDIN_ARG;
+
+/// The streams of the virtual machine, owned by it alone. The memory
+/// components only return errors, so nothing else has to share them.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct InputOutput<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> {
-    stdin: Rc<RefCell<R>>,
-    stdout: Rc<RefCell<W>>,
-    stderr: Rc<RefCell<E>>,
+    stdin: R,
+    stdout: W,
+    stderr: E,
 }
 
-impl Default for InputOutput<StdinLock<'_>, StdoutLock<'_>, StderrLock<'_>> {
+/// Buffers stdout, which is flushed before reading input and when the
+/// virtual machine stops.
+impl Default
+    for InputOutput<StdinLock<'_>, BufWriter<StdoutLock<'_>>, StderrLock<'_>>
+{
     fn default() -> Self {
         let stdin = std::io::stdin();
         let stdout = std::io::stdout();
         let stderr = std::io::stderr();
-        Self::new(stdin.lock(), stdout.lock(), stderr.lock())
+        Self::new(stdin.lock(), BufWriter::new(stdout.lock()), stderr.lock())
     }
 }
 
@@ -25,39 +35,34 @@ impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug>
 {
     pub fn new(stdin: R, stdout: W, stderr: E) -> Self {
         Self {
-            stdin: Rc::new(RefCell::new(stdin)),
-            stdout: Rc::new(RefCell::new(stdout)),
-            stderr: Rc::new(RefCell::new(stderr)),
+            stdin,
+            stdout,
+            stderr,
         }
     }
 
-    pub fn stdin_borrow(&self) -> std::cell::Ref<'_, R> {
-        self.stdin.borrow()
-    }
-
-    pub fn stdout_borrow(&self) -> std::cell::Ref<'_, W> {
-        self.stdout.borrow()
+    /// Returns the streams, e.g. to collect the output of a program.
+    pub fn into_parts(self) -> (R, W, E) {
+        (self.stdin, self.stdout, self.stderr)
     }
 
-    pub fn stderr_borrow(&self) -> std::cell::Ref<'_, E> {
-        self.stderr.borrow()
+    pub fn stdin(&mut self) -> &mut R {
+        &mut self.stdin
     }
 
-    pub fn stdin_borrow_mut(&self) -> std::cell::RefMut<'_, R> {
-        self.stdin.borrow_mut()
+    pub fn stdout(&mut self) -> &mut W {
+        &mut self.stdout
     }
 
-    pub fn stdout_borrow_mut(&self) -> std::cell::RefMut<'_, W> {
-        self.stdout.borrow_mut()
+    pub fn stderr(&mut self) -> &mut E {
+        &mut self.stderr
     }
 
-    pub fn stderr_borrow_mut(&self) -> std::cell::RefMut<'_, E> {
-        self.stderr.borrow_mut()
-    }
-
-    pub fn read_line(&self) -> Option<String> {
+    /// Reads a line after flushing stdout, so prompts are visible.
+    pub fn read_line(&mut self) -> Option<String> {
+        self.flush_stdout();
         let mut buffer = String::new();
-        let bytes_read = self.stdin.borrow_mut().read_line(&mut buffer);
+        let bytes_read = self.stdin.read_line(&mut buffer);
         match bytes_read {
             Ok(0) => None, // EOF
             Ok(_) => Some(buffer),
@@ -69,153 +74,91 @@ impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug>
         }
     }
 
-    pub fn write_stdout(&self, message: &str) {
-        if let Err(err) = self.stdout.borrow_mut().write_all(message.as_bytes())
-        {
-            let message = format!("Error reading from stdout: {err:?}\n");
+    pub fn write_stdout(&mut self, message: &str) {
+        self.print(format_args!("{message}"));
+    }
+
+    /// Formats `args` straight into stdout.
+    pub fn print(&mut self, args: fmt::Arguments<'_>) {
+        if let Err(err) = self.stdout.write_fmt(args) {
+            let message = format!("Error writing to stdout: {err:?}\n");
             self.write_stderr(&message);
         }
     }
 
-    pub fn write_stderr(&self, message: &str) {
-        if let Err(err) = self.stderr.borrow_mut().write_all(message.as_bytes())
-        {
+    pub fn write_stderr(&mut self, message: &str) {
+        if let Err(err) = self.stderr.write_all(message.as_bytes()) {
             eprintln!("Critical error: Unable to write to stderr: {err:?}",);
         }
     }
 
-    pub fn flush_stdout(&self) {
-        if let Err(err) = self.stdout.borrow_mut().flush() {
+    pub fn flush_stdout(&mut self) {
+        if let Err(err) = self.stdout.flush() {
             self.write_stderr(&format!("Failed to flush stdout: {err:?}\n"));
         }
     }
 
-    pub fn flush_stderr(&self) {
-        if let Err(err) = self.stderr.borrow_mut().flush() {
+    pub fn flush_stderr(&mut self) {
+        if let Err(err) = self.stderr.flush() {
             eprintln!("Critical error: Unable to flush stderr: {err:?}");
         }
     }
 
-    pub fn fatal_error(&self, error: &str) -> ! {
-        self.write_stderr(error);
-        self.flush_stderr();
-        #[cfg(not(test))]
-        std::process::exit(1);
-        #[cfg(test)]
-        panic!("{error}\n");
-    }
-
-    pub fn check_ninja_version(&self, file: &[u8]) {
-        let version = match file
-            .chunks(4)
-            .nth(1)
-            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
-        {
-            Some(version) => version,
-            None => self.fatal_error("Failed to read version\n"),
-        };
-        if VERSION != version as u8 {
-            self.fatal_error(
-                "Error: code file does not have correct Ninja version\n",
-            )
-        }
-    }
-
-    pub fn check_instructions(&self, file: &[u8]) -> usize {
-        match file
-            .chunks(4)
-            .nth(2)
-            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
-        {
-            Some(count) => match count.try_into() {
-                Ok(count) => count,
-                Err(err) => {
-                    let message = format!(
-                        "Error: failed to parse to usize from u32: {err}\n"
-                    );
-                    self.fatal_error(&message)
-                }
-            },
-            None => {
-                self.fatal_error("Error: failed to read instruction count\n")
-            }
+    pub fn read_file(&self, arg: &str) -> Result<Vec<u8>, VmError> {
+        if arg.trim().is_empty() {
+            return Err(LoadError::NoCodeFile.into());
         }
-    }
 
-    pub fn check_ninja_format(&self, file: &[u8], arg: &str) {
-        let ninja_binary_format = &[78, 74, 66, 70];
-        if !file.starts_with(ninja_binary_format) {
-            let message =
-                format!("Error: file '{arg}' is not a Ninja binary\n");
-            self.fatal_error(&message);
-        }
+        std::fs::read(arg).map_err(|source| {
+            let file = String::from(arg);
+            LoadError::CannotOpen { file, source }.into()
+        })
     }
 
-    pub fn check_variables(&self, file: &[u8]) -> usize {
-        match file
-            .chunks(4)
-            .nth(3)
-            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
-        {
-            Some(count) => match count.try_into() {
-                Ok(count) => count,
-                Err(err) => {
-                    let message = format!(
-                        "Error: failed to parse to usize from u32: {err}\n"
-                    );
-                    self.fatal_error(&message)
-                }
-            },
-            None => self
-                .fatal_error("Error: failed to read global variable count\n"),
-        }
+    pub fn read_stdin(&mut self) -> Result<Vec<u8>, VmError> {
+        let mut file = Vec::new();
+        self.stdin.read_to_end(&mut file).map_err(|source| {
+            let file = String::from(STDIN_ARG);
+            LoadError::CannotOpen { file, source }
+        })?;
+        Ok(file)
     }
 
-    pub fn read_file(&self, arg: &str) -> Vec<u8> {
-        if arg.trim().is_empty() {
-            self.fatal_error("Error: no code file specified\n");
+    pub fn verify_arg(&self, arg: &str) -> Result<(), VmError> {
+        if arg.starts_with('-') {
+            return Err(VmError::unknown_argument(arg));
         }
-
-        std::fs::read(arg).unwrap_or_else(|err| {
-            let error =
-                format!("Error: cannot open code file '{arg}': {err}\n");
-            self.fatal_error(&error);
-        })
+        Ok(())
     }
+}
 
-    pub fn set_ninja_version(&self, file: &mut [u8]) {
-        let version = match file.chunks_mut(4).nth(1).map(|c| {
-            c[0] = VERSION;
-            u32::from_le_bytes([c[0], c[1], c[2], c[3]])
-        }) {
-            Some(version) => version,
-            None => self.fatal_error("Failed to read version\n"),
-        };
-        if VERSION != version as u8 {
-            self.fatal_error(
-                "Error: code file does not have correct Ninja version\n",
-            )
-        }
-    }
+/// Reads the little endian words and the names of the sections after the
+/// instructions of a binary. A name is the number of its UTF-8 bytes
+/// followed by the bytes, and running out of bytes fails with `error`.
+pub(crate) struct Reader<'a> {
+    pub bytes: &'a [u8],
+    pub error: HeaderError,
+}
 
-    pub fn split_file_metadata(&self, file: &mut Vec<u8>) -> Vec<u8> {
-        if file.len() < 16 {
-            self.fatal_error("Error: code file is corrupted\n")
+impl Reader<'_> {
+    fn take(&mut self, count: usize) -> Result<&[u8], HeaderError> {
+        if self.bytes.len() < count {
+            return Err(self.error);
         }
-        file.split_off(16)
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
     }
 
-    pub fn unknown_arg(&self, arg: &str) {
-        let message = format!(
-            "unknown command line argument '{arg}', try './njvm --help'\n"
-        );
-        self.fatal_error(&message);
+    pub fn word(&mut self) -> Result<u32, HeaderError> {
+        let word = self.take(4)?;
+        Ok(u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
     }
 
-    pub fn verify_arg(&self, arg: &str) {
-        if arg.starts_with('-') {
-            self.unknown_arg(arg)
-        }
+    pub fn name(&mut self) -> Result<String, HeaderError> {
+        let length = self.word()? as usize;
+        let name = self.take(length)?;
+        String::from_utf8(name.to_vec()).or(Err(self.error))
     }
 }
 
@@ -228,10 +171,11 @@ mod tests {
         let stdin = b"";
         let mut stdout = Vec::new();
         let stderr = Vec::new();
-        let io = InputOutput::new(&stdin[..], &mut stdout, stderr);
+        let mut io = InputOutput::new(&stdin[..], &mut stdout, stderr);
         let input = "test";
         io.write_stdout(input);
+        io.print(format_args!(" {}", 42));
         let result = String::from_utf8(stdout).unwrap();
-        assert_eq!(result, input);
+        assert_eq!(result, "test 42");
     }
 }
@@ -1,113 +1,224 @@
+pub mod asm;
 pub mod cpu;
+pub mod error;
 pub mod io;
 pub mod memory;
 
-use std::cell::RefCell;
 use std::fmt::Debug;
-use std::io::{BufRead, StderrLock, StdinLock, StdoutLock, Write};
-use std::rc::Rc;
+use std::io::{
+    BufRead, BufWriter, ErrorKind, Read, StderrLock, StdinLock, StdoutLock,
+    Write,
+};
 
-use cpu::immediate::Immediate;
+use asm::symbols::SymbolMap;
 use cpu::instruction::Instruction;
+use cpu::opcode::Opcode;
+use cpu::profile::VersionProfile;
+use error::{LoadError, VmError};
+use io::constant_pool::ConstantPool;
+use io::debug_info::DebugInfo;
+use io::header::{Binary, Header};
 use io::InputOutput;
 use memory::heap::{Heap, DEFAULT_HEAP_MEMORY};
 use memory::instruction_register::{Bytecode, InstructionRegister};
-use memory::stack::Stack;
+use memory::stack::{Stack, StackSlot, DEFAULT_STACK_MEMORY};
 use memory::static_data_area::StaticDataArea;
+use njvm_cli::{Args, Command, UsageError};
 
-pub const VERSION: u8 = 4;
+pub const VERSION: u32 = 8;
+
+/// The code file argument that reads the binary from stdin.
+pub const STDIN_ARG: &str = "-";
+/// The name of a binary loaded from memory in error messages.
+const MEMORY_NAME: &str = "<memory>";
+
+/// Why [`NinjaVM::run_for`] returned.
+#[derive(Debug)]
+pub enum RunState {
+    /// The program executed `halt`.
+    Halted,
+    /// The program used up its steps. Running it again resumes it.
+    OutOfFuel,
+    /// The next instruction reads input, but stdin has not buffered all
+    /// of it yet. Running the program again retries the instruction.
+    WaitingForInput,
+    /// The program stopped with an error.
+    Trapped(VmError),
+}
 
 pub type Breakpoint = usize;
-pub type ReturnValueRegister = Immediate;
+pub type ReturnValueRegister = StackSlot;
 
+/// A Ninja virtual machine, which owns its memory and its streams.
+///
+/// A virtual machine is `Send` if its streams are, so independent machines
+/// can run on worker threads, each with its own input and output buffers.
+/// Arithmetic on big integers is serialized between threads, everything
+/// else runs in parallel.
+///
+/// ```
+/// use njvm::error::VmError;
+/// use njvm::io::InputOutput;
+/// use njvm::NinjaVM;
+///
+/// let binary = std::fs::read("assets/a3/prog1.bin").unwrap();
+/// let outputs = std::thread::scope(|scope| {
+///     let workers: Vec<_> = ["12 18\n", "35 14\n"]
+///         .into_iter()
+///         .map(|input| {
+///             let binary = &binary;
+///             scope.spawn(move || {
+///                 let io = InputOutput::new(input.as_bytes(), Vec::new(), Vec::new());
+///                 let mut vm = NinjaVM::from_reader(io, &binary[..])?;
+///                 vm.init();
+///                 vm.work()?;
+///                 let (_, stdout, _) = vm.into_io().into_parts();
+///                 Ok::<_, VmError>(stdout)
+///             })
+///         })
+///         .collect();
+///     workers.into_iter().map(|worker| worker.join().unwrap().unwrap()).collect::<Vec<_>>()
+/// });
+/// assert!(outputs[0].ends_with(b"6\nNinja Virtual Machine stopped\n"));
+/// assert!(outputs[1].ends_with(b"7\nNinja Virtual Machine stopped\n"));
+/// ```
 #[derive(Debug, Clone)]
 pub struct NinjaVM<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> {
-    io: Rc<RefCell<InputOutput<R, W, E>>>,
-    stack: Stack<R, W, E, Immediate>,
-    heap: Heap<R, W, E>,
-    ir: InstructionRegister<R, W, E>,
-    sda: StaticDataArea<R, W, E, Immediate>,
+    io: InputOutput<R, W, E>,
+    stack: Stack<StackSlot>,
+    heap: Heap,
+    ir: InstructionRegister,
+    sda: StaticDataArea<StackSlot>,
     bp: Option<Breakpoint>,
     rv: Option<ReturnValueRegister>,
+    profile: Option<VersionProfile>,
+    verify_on_load: bool,
+    symbols: SymbolMap,
+    constants: ConstantPool,
+    debug_info: DebugInfo,
 }
 
-impl Default for NinjaVM<StdinLock<'_>, StdoutLock<'_>, StderrLock<'_>> {
+impl Default
+    for NinjaVM<StdinLock<'_>, BufWriter<StdoutLock<'_>>, StderrLock<'_>>
+{
     fn default() -> Self {
         NinjaVM::new(InputOutput::default())
     }
 }
 
 impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
-    pub fn start(args: Vec<String>) {
-        let mut vm = NinjaVM::default();
+    pub fn start(args: Vec<String>) -> Result<(), VmError> {
+        let args = Args::try_parse_args(args)?;
+        let stack = args.stack.unwrap_or(DEFAULT_STACK_MEMORY);
+        let heap = args.heap.unwrap_or(DEFAULT_HEAP_MEMORY);
+        let mut vm = NinjaVM::with_memory(InputOutput::default(), stack, heap)?;
 
-        if args.is_empty() {
-            vm.io_borrow()
-                .fatal_error("Error: no code file specified\n");
+        if args.help {
+            vm.help();
+            return Ok(());
+        }
+        if args.version {
+            vm.version();
+            return Ok(());
         }
 
-        let mut debug_mode = false;
-        let mut file: Option<String> = None;
+        vm.heap.stats = args.gcstats;
+        vm.heap.purge = args.gcpurge;
+        if let Some(version) = args.profile {
+            let profile = VersionProfile::new(version)
+                .ok_or(UsageError::IllegalValue("version profile"))?;
+            vm.set_profile(Some(profile));
+        }
+        vm.set_verify(args.verify);
+        if let Some(map) = &args.map {
+            vm.set_symbols(asm::read_map(map)?);
+        }
 
-        for arg in args.into_iter() {
-            match arg.as_str() {
-                "--help" => {
-                    vm.help();
-                    return;
-                }
-                "--version" => {
-                    vm.version();
-                    return;
-                }
-                "--debug" => {
-                    if debug_mode {
-                        vm.io_borrow()
-                            .fatal_error("Error: duplicate '--debug' flag\n");
-                    }
-                    debug_mode = true;
-                }
-                _ if arg.starts_with('-') => vm.io_borrow().unknown_arg(&arg),
-                _ => {
-                    if file.is_some() {
-                        vm.io_borrow().fatal_error(
-                            "Error: more than one code file specified\n",
-                        );
-                    }
-                    file = Some(arg);
-                }
+        match args.command {
+            Some(Command::Verify { file }) => {
+                vm.load(&file)?;
+                vm.verify()?;
+                let code_size = vm.ir.data.len();
+                let data_size = vm.sda.data.len();
+                vm.io.write_stdout(&format!(
+                    "Ninja binary '{file}' verified (code size = {code_size}, data size = {data_size})\n"
+                ));
+                return Ok(());
+            }
+            Some(Command::Asm {
+                input,
+                output,
+                map,
+                debug_info,
+            }) => {
+                return vm.assemble_file(
+                    &input,
+                    &output,
+                    map.as_deref(),
+                    debug_info,
+                );
             }
+            Some(Command::Disasm { input, output, map }) => {
+                return vm.disassemble_file(
+                    &input,
+                    output.as_deref(),
+                    map.as_deref(),
+                );
+            }
+            None => {}
         }
 
-        let file = file.unwrap_or_else(|| {
-            vm.io_borrow()
-                .fatal_error("Error: no code file specified\n")
-        });
+        let file = args.file.ok_or(LoadError::NoCodeFile)?;
 
-        if debug_mode {
-            vm.debug(&file);
-        } else {
-            vm.execute_binary(&file);
+        match (args.debug, args.max_steps) {
+            (true, _) => vm.debug(&file),
+            (false, None) => vm.execute_binary(&file),
+            (false, Some(steps)) => vm.execute_binary_for(&file, steps),
         }
     }
 
     pub fn new(io: InputOutput<R, W, E>) -> Self {
-        let io = Rc::new(RefCell::new(io));
-
-        Self {
-            io: io.clone(),
-            stack: Stack::new(io.clone()),
-            heap: Heap::new(io.clone(), DEFAULT_HEAP_MEMORY),
-            ir: InstructionRegister::new(io.clone(), 0, 0),
-            sda: StaticDataArea::new(io.clone(), 0, 0),
+        Self::with_memory(io, DEFAULT_STACK_MEMORY, DEFAULT_HEAP_MEMORY)
+            .expect("the default memory sizes can be allocated")
+    }
+
+    /// Creates a virtual machine with a stack of `stack` KiB and a heap of
+    /// `heap` KiB, which fails if the memory cannot be allocated.
+    pub fn with_memory(
+        io: InputOutput<R, W, E>,
+        stack: usize,
+        heap: usize,
+    ) -> Result<Self, VmError> {
+        Ok(Self {
+            io,
+            stack: Stack::try_new(stack)?,
+            heap: Heap::try_new(heap)?,
+            ir: InstructionRegister::new(0, 0),
+            sda: StaticDataArea::new(0, StackSlot::default()),
             bp: None,
             rv: None,
-        }
+            profile: None,
+            verify_on_load: false,
+            symbols: SymbolMap::new(),
+            constants: ConstantPool::default(),
+            debug_info: DebugInfo::default(),
+        })
+    }
+
+    /// Decodes and executes `bytecode`, which traps if its opcode is
+    /// undefined.
+    pub fn execute_instruction(
+        &mut self,
+        bytecode: Bytecode,
+    ) -> Result<(), VmError> {
+        self.execute(Instruction::decode(bytecode)?)
     }
 
-    pub fn execute_instruction(&mut self, bytecode: Bytecode) {
+    /// Executes a decoded instruction.
+    #[inline]
+    pub fn execute(&mut self, instruction: Instruction) -> Result<(), VmError> {
         use cpu::opcode::Opcode::*;
 
-        let instruction = Instruction::from(bytecode);
         let immediate = instruction.immediate;
 
         match instruction.opcode {
@@ -143,99 +254,338 @@ impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
             Pushr => self.pushr(),
             Popr => self.popr(),
             Dup => self.dup(),
+            New => self.new_object(immediate),
+            Getf => self.getf(immediate),
+            Putf => self.putf(immediate),
+            Newa => self.newa(),
+            Getfa => self.getfa(),
+            Putfa => self.putfa(),
+            Getsz => self.getsz(),
+            Pushn => self.pushn(),
+            Refeq => self.refeq(),
+            Refne => self.refne(),
+            Pushk => self.pushk(immediate),
         }
     }
 
-    pub fn work(&mut self) {
+    pub fn work(&mut self) -> Result<(), VmError> {
         loop {
-            let bytecode = self.ir.data[self.ir.pc];
-            let decoded = Instruction::from(bytecode);
-            let opcode = decoded.opcode;
-            self.ir.pc += 1;
-            self.execute_instruction(bytecode);
-            if opcode == cpu::opcode::Opcode::Halt {
-                break;
+            let instruction =
+                self.ir.instruction(self.ir.pc).and_then(|instruction| {
+                    self.ir.pc += 1;
+                    self.execute(instruction).map(|()| instruction)
+                });
+            let instruction = instruction.map_err(|error| {
+                self.io.flush_stdout();
+                self.traced(error)
+            })?;
+            if instruction.opcode == Opcode::Halt {
+                return Ok(());
             }
         }
     }
 
-    pub fn execute_binary(&mut self, bin: &str) {
-        let instructions = self.load_binary(bin);
-        self.load_instructions(&instructions);
+    /// Executes at most `steps` instructions of the program, stopping early
+    /// if it halts, traps or waits for input. Stdout is flushed before
+    /// returning.
+    ///
+    /// A read waits unless stdin has buffered the whole input it takes:
+    /// a character for `rdchr` and an integer up to the byte after it for
+    /// `rdint`. Only a non-blocking stdin, which returns
+    /// [`ErrorKind::WouldBlock`] or an empty buffer while no input has
+    /// arrived, lets the program wait instead of blocking the caller.
+    pub fn run_for(&mut self, steps: u64) -> RunState {
+        self.run_steps(steps, true)
+    }
+
+    /// Executes at most `steps` instructions, returning
+    /// [`RunState::WaitingForInput`] before a read without buffered input
+    /// if `wait` is set and blocking on stdin otherwise.
+    fn run_steps(&mut self, steps: u64, wait: bool) -> RunState {
+        let state = 'run: {
+            for _ in 0..steps {
+                let instruction = match self.ir.instruction(self.ir.pc) {
+                    Ok(instruction) => instruction,
+                    Err(error) => {
+                        break 'run RunState::Trapped(self.traced(error));
+                    }
+                };
+                let opcode = instruction.opcode;
+                if wait && !self.input_available(opcode) {
+                    break 'run RunState::WaitingForInput;
+                }
+                self.ir.pc += 1;
+                if let Err(error) = self.execute(instruction) {
+                    break 'run RunState::Trapped(self.traced(error));
+                }
+                if opcode == Opcode::Halt {
+                    break 'run RunState::Halted;
+                }
+            }
+            RunState::OutOfFuel
+        };
+        self.io.flush_stdout();
+        state
+    }
+
+    /// Returns whether stdin has buffered all input that `opcode` reads.
+    /// Read errors other than [`ErrorKind::WouldBlock`] count as available,
+    /// so the instruction reports them.
+    fn input_available(&mut self, opcode: Opcode) -> bool {
+        if !matches!(opcode, Opcode::Rdint | Opcode::Rdchr) {
+            return true;
+        }
+        let buffer = match self.io.stdin().fill_buf() {
+            Ok(buffer) => buffer,
+            Err(error) => return error.kind() != ErrorKind::WouldBlock,
+        };
+        if opcode == Opcode::Rdchr {
+            return !buffer.is_empty();
+        }
+        // rdint skips spaces and reads the digits up to the byte after them
+        let mut bytes = buffer.iter().skip_while(|&&byte| byte == b' ');
+        match bytes.next() {
+            Some(b'-' | b'0'..=b'9') => {
+                bytes.any(|byte| !byte.is_ascii_digit())
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    pub fn execute_binary(&mut self, bin: &str) -> Result<(), VmError> {
+        self.load(bin)?;
+        self.init();
+        self.work()
+    }
+
+    /// Executes the code file `bin` for at most `steps` instructions.
+    pub fn execute_binary_for(
+        &mut self,
+        bin: &str,
+        steps: u64,
+    ) -> Result<(), VmError> {
+        self.load(bin)?;
         self.init();
-        self.work();
-    }
-
-    pub fn load_binary(&mut self, arg: &str) -> Vec<u8> {
-        self.io_borrow().verify_arg(arg);
-        let mut file = self.io_borrow().read_file(arg);
-        let instructions = self.io_borrow().split_file_metadata(&mut file);
-        self.io_borrow().check_ninja_format(&file, arg);
-        self.io_borrow().check_ninja_version(&file);
-        let variable_count = self.io_borrow().check_variables(&file);
-        let instruction_count = self.io_borrow().check_instructions(&file);
-        self.sda.data.resize(variable_count, 0);
-        self.ir.data.resize(instruction_count, 0);
-        instructions
-    }
-
-    pub fn load_test_binary(&mut self, arg: &str) -> Vec<u8> {
-        self.io_borrow().verify_arg(arg);
-        let mut file = self.io_borrow().read_file(arg);
-        let instructions = self.io_borrow().split_file_metadata(&mut file);
-        self.io_borrow().check_ninja_format(&file, arg);
-        self.io_borrow().set_ninja_version(&mut file);
-        let variable_count = self.io_borrow().check_variables(&file);
-        let instruction_count = self.io_borrow().check_instructions(&file);
-        self.sda.data.resize(variable_count, 0);
-        self.ir.data.resize(instruction_count, 0);
-        instructions
+        match self.run_steps(steps, false) {
+            RunState::Halted => Ok(()),
+            RunState::OutOfFuel => Err(VmError::StepLimit { steps }),
+            RunState::WaitingForInput => unreachable!("reads block on stdin"),
+            RunState::Trapped(error) => Err(error),
+        }
+    }
+
+    /// Reads and checks the code file `arg`, which is read from stdin if
+    /// it is `-`, and returns its instructions.
+    pub fn load_binary(&mut self, arg: &str) -> Result<Vec<u8>, VmError> {
+        let file = self.read_binary(arg)?;
+        self.check_binary(file, arg)
+    }
+
+    /// Checks a Ninja binary held in memory and loads its instructions.
+    pub fn load_bytes(&mut self, bytes: &[u8]) -> Result<(), VmError> {
+        let instructions = self.check_binary(bytes.to_vec(), MEMORY_NAME)?;
+        self.load_instructions(&instructions);
+        self.verify_loaded()
+    }
+
+    /// Creates a virtual machine and loads the Ninja binary read from
+    /// `reader` into it.
+    pub fn from_reader(
+        io: InputOutput<R, W, E>,
+        mut reader: impl Read,
+    ) -> Result<Self, VmError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|err| VmError::io("read code file", err))?;
+        let mut vm = NinjaVM::new(io);
+        vm.load_bytes(&bytes)?;
+        Ok(vm)
+    }
+
+    fn read_binary(&mut self, arg: &str) -> Result<Vec<u8>, VmError> {
+        if arg == STDIN_ARG {
+            return self.io.read_stdin();
+        }
+        self.io.verify_arg(arg)?;
+        self.io.read_file(arg)
+    }
+
+    /// Returns the streams, e.g. to feed more input to a program that is
+    /// waiting for it.
+    pub fn io_mut(&mut self) -> &mut InputOutput<R, W, E> {
+        &mut self.io
+    }
+
+    /// Consumes the virtual machine and returns its streams, e.g. to
+    /// collect the output of a program.
+    pub fn into_io(mut self) -> InputOutput<R, W, E> {
+        self.io.flush_stdout();
+        self.io
+    }
+
+    /// Forces the instruction set of `profile` on the binaries loaded from
+    /// now on. Without a profile the version of each binary selects it.
+    pub fn set_profile(&mut self, profile: Option<VersionProfile>) {
+        self.profile = profile;
+    }
+
+    /// Makes loading verify programs before they run, see
+    /// [`verify`](cpu::verifier::verify).
+    pub fn set_verify(&mut self, verify: bool) {
+        self.verify_on_load = verify;
+    }
+
+    fn verify_loaded(&self) -> Result<(), VmError> {
+        if self.verify_on_load {
+            self.verify()?;
+        }
+        Ok(())
+    }
+
+    /// Validates the header of `file`, that every instruction is defined by
+    /// its version profile, that every branch target lies inside the
+    /// program and that every constant exists, sizes the static data area
+    /// and the instruction register accordingly, keeps the constant pool and
+    /// the debug information and returns the instructions.
+    fn check_binary(
+        &mut self,
+        file: Vec<u8>,
+        name: &str,
+    ) -> Result<Vec<u8>, VmError> {
+        let Binary {
+            header,
+            code: instructions,
+            constants,
+            debug_info,
+        } = Header::split(&file).map_err(|source| {
+            LoadError::InvalidBinary {
+                file: String::from(name),
+                source,
+            }
+        })?;
+        let profile = match self.profile {
+            Some(profile) => profile,
+            None => VersionProfile::new(header.version)
+                .expect("the header has a known version"),
+        };
+        let count = header.instruction_count as usize;
+        let words = instructions.chunks_exact(4);
+        for (address, c) in words.enumerate() {
+            let bytecode = u32::from_le_bytes([c[0], c[1], c[2], c[3]]);
+            if !profile.supports(bytecode) {
+                return Err(LoadError::IllegalInstruction {
+                    bytecode,
+                    address,
+                }
+                .into());
+            }
+            let Instruction { opcode, immediate } = Instruction::from(bytecode);
+            if opcode.is_branch()
+                && !usize::try_from(immediate).is_ok_and(|pc| pc < count)
+            {
+                return Err(LoadError::InvalidTarget {
+                    opcode,
+                    target: immediate,
+                    address,
+                    count,
+                }
+                .into());
+            }
+            let constant_count = constants.as_ref().map_or(0, |c| c.len());
+            if opcode == Opcode::Pushk
+                && !usize::try_from(immediate)
+                    .is_ok_and(|index| index < constant_count)
+            {
+                return Err(LoadError::InvalidConstant {
+                    index: immediate,
+                    address,
+                    count: constant_count,
+                }
+                .into());
+            }
+        }
+        let global_count = header.global_count as usize;
+        self.sda.data.resize(global_count, StackSlot::default());
+        self.ir.resize_data(count, 0);
+        self.constants = constants.unwrap_or_default();
+        self.debug_info = debug_info.unwrap_or_default();
+        Ok(instructions.to_vec())
     }
 
     pub fn load_instructions(&mut self, instructions: &[u8]) {
-        instructions.chunks(4).for_each(|c| {
+        self.ir.pc = 0;
+        instructions.chunks_exact(4).for_each(|c| {
             let bytecode = u32::from_le_bytes([c[0], c[1], c[2], c[3]]);
             let instruction = Instruction::from(bytecode);
             let opcode = instruction.opcode;
             let immediate = instruction.immediate;
-            self.ir.register_instruction(opcode, immediate);
+            self.ir
+                .register_instruction(opcode, immediate)
+                .expect("a decoded immediate fits into an instruction");
         });
     }
 
-    pub fn load(&mut self, bin: &str) {
-        let instructions = self.load_binary(bin);
-        self.load_instructions(&instructions)
+    pub fn load(&mut self, bin: &str) -> Result<(), VmError> {
+        let instructions = self.load_binary(bin)?;
+        self.load_instructions(&instructions);
+        self.verify_loaded()
     }
 
     pub fn init(&mut self) {
-        self.io_borrow()
-            .write_stdout("Ninja Virtual Machine started\n");
+        self.io.write_stdout("Ninja Virtual Machine started\n");
         self.ir.pc = 0;
     }
 
-    fn help(&self) {
-        self.io_borrow()
-            .write_stdout("usage: ./njvm [options] <code file>\n");
-        self.io_borrow().write_stdout("Options:\n");
-        self.io_borrow().write_stdout(
+    fn help(&mut self) {
+        self.io
+            .write_stdout("Usage: ./njvm [options] <code file>\n");
+        self.io.write_stdout("       ./njvm verify <code file>\n");
+        self.io.write_stdout(
+            "       ./njvm asm <asm file> -o <code file> [--map <map file>] [-g]\n",
+        );
+        self.io.write_stdout(
+            "       ./njvm disasm <code file> [-o <asm file>] [--map <map file>]\n",
+        );
+        self.io.write_stdout("Options:\n");
+        self.io.write_stdout(&format!(
+            "  --stack <n>      set stack size to n KBytes (default: n = {DEFAULT_STACK_MEMORY})\n"
+        ));
+        self.io.write_stdout(&format!(
+            "  --heap <n>       set heap size to n KBytes (default: n = {DEFAULT_HEAP_MEMORY})\n"
+        ));
+        self.io.write_stdout(
+            "  --gcstats        show garbage collection statistics\n",
+        );
+        self.io.write_stdout(
+            "  --gcpurge        purge old objects after collection\n",
+        );
+        self.io.write_stdout(
             "  --debug          start virtual machine in debug mode\n",
         );
-        self.io_borrow()
+        self.io.write_stdout(
+            "  --profile <n>    run binaries with the instructions of version n\n",
+        );
+        self.io.write_stdout(
+            "  --verify         verify the code file before running it\n",
+        );
+        self.io.write_stdout(
+            "  --max-steps <n>  stop with exit code 124 after n instructions\n",
+        );
+        self.io.write_stdout(
+            "  --map <file>     name addresses after the labels of a map file\n",
+        );
+        self.io
             .write_stdout("  --version        show version and exit\n");
-        self.io_borrow()
+        self.io
             .write_stdout("  --help           show this help and exit\n");
     }
 
-    fn version(&self) {
-        self.io_borrow().write_stdout("Ninja Virtual Machine version {VERSION} (compiled Sep 23 2015, 10:36:52)\n",);
-    }
-
-    pub fn io_borrow(&self) -> std::cell::Ref<'_, InputOutput<R, W, E>> {
-        self.io.borrow()
-    }
-
-    pub fn io_borrow_mut(&self) -> std::cell::RefMut<'_, InputOutput<R, W, E>> {
-        self.io.borrow_mut()
+    fn version(&mut self) {
+        self.io.write_stdout(&format!(
+            "Ninja Virtual Machine version {VERSION} (compiled Sep 23 2015, 10:37:03)\n"
+        ));
     }
 }
 
@@ -243,6 +593,7 @@ impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
 mod tests {
     use super::*;
     use cpu::opcode::Opcode::*;
+    use error::{HeaderError, Trap, VerifyError};
 
     #[test]
     fn test_ninja_vm() {
@@ -271,11 +622,11 @@ mod tests {
             &mut stderr,
         ));
         vm.ir.resize_data(3, 0);
-        vm.ir.register_instruction(Pushc, 1);
-        vm.ir.register_instruction(Pushc, 2);
-        vm.ir.register_instruction(Halt, 0);
+        vm.ir.register_instruction(Pushc, 1).unwrap();
+        vm.ir.register_instruction(Pushc, 2).unwrap();
+        vm.ir.register_instruction(Halt, 0).unwrap();
         vm.init();
-        vm.work();
+        vm.work().unwrap();
         assert_eq!(vm.stack.data.len(), 2);
     }
 
@@ -289,10 +640,14 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        let instruction = Instruction::encode_instruction(Pushc, 1);
-        vm.execute_instruction(instruction);
+        let instruction = Instruction::encode_instruction(Pushc, 1).unwrap();
+        vm.execute_instruction(instruction).unwrap();
+        assert_eq!(vm.stack.sp, 1);
+        assert_eq!(vm.integer_value(vm.stack.data[0]).unwrap(), 1);
+        let result = vm.execute_instruction(0xFF000001);
+        let expected = Trap::UnknownOpcode { opcode: 0xFF };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
         assert_eq!(vm.stack.sp, 1);
-        assert_eq!(vm.stack.data[0], 1);
     }
 
     #[test]
@@ -319,10 +674,10 @@ mod tests {
             &mut stdout,
             &mut stderr,
         ));
-        let instructions = vm.load_test_binary("assets/a4/prog02.bin");
+        let instructions = vm.load_binary("assets/a4/prog02.bin").unwrap();
         vm.load_instructions(&instructions);
         vm.init();
-        vm.work();
+        vm.work().unwrap();
         let output = String::from_utf8(stdout).unwrap();
         let expected = r#"Ninja Virtual Machine started
 11
@@ -343,10 +698,10 @@ Ninja Virtual Machine stopped
             &mut stdout,
             &mut stderr,
         ));
-        let instructions = vm.load_test_binary("assets/a4/prog12.bin");
+        let instructions = vm.load_binary("assets/a4/prog12.bin").unwrap();
         vm.load_instructions(&instructions);
         vm.init();
-        vm.work();
+        vm.work().unwrap();
         let output = String::from_utf8(stdout).unwrap();
         let expected = r#"Ninja Virtual Machine started
 5! = 120
@@ -354,4 +709,442 @@ Ninja Virtual Machine stopped
 "#;
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn test_prog_a7_listrev() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let instructions = vm.load_binary("assets/a7/listrev.bin").unwrap();
+        vm.load_instructions(&instructions);
+        vm.init();
+        vm.work().unwrap();
+        let output = String::from_utf8(stdout).unwrap();
+        let expected =
+            std::fs::read_to_string("assets/a7/listrev.out").unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_prog_a7_twodim() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let instructions = vm.load_binary("assets/a7/twodim.bin").unwrap();
+        vm.load_instructions(&instructions);
+        vm.init();
+        vm.work().unwrap();
+        let output = String::from_utf8(stdout).unwrap();
+        let expected = std::fs::read_to_string("assets/a7/twodim.out").unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_prog_a7_matinv() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let instructions = vm.load_binary("assets/a7/matinv.bin").unwrap();
+        vm.load_instructions(&instructions);
+        vm.init();
+        vm.work().unwrap();
+        let output = String::from_utf8(stdout).unwrap();
+        let expected = std::fs::read_to_string("assets/a7/matinv.out").unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_prog_a8_factor() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let instructions = vm.load_binary("assets/a8/factor.bin").unwrap();
+        vm.load_instructions(&instructions);
+        vm.init();
+        vm.work().unwrap();
+        let output = String::from_utf8(stdout).unwrap();
+        let expected = std::fs::read_to_string("assets/a8/factor.out").unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_load_binaries_of_every_version() {
+        for assignment in ["a1", "a2", "a3", "a4", "a7", "a8"] {
+            let directory = format!("assets/{assignment}");
+            for entry in std::fs::read_dir(directory).unwrap() {
+                let path = entry.unwrap().path();
+                if path.extension().is_some_and(|ext| ext == "bin") {
+                    let stdin = b"";
+                    let mut vm = NinjaVM::new(InputOutput::new(
+                        &stdin[..],
+                        Vec::new(),
+                        Vec::new(),
+                    ));
+                    vm.load(path.to_str().unwrap()).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_profile_rejects_undefined_instructions() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.set_profile(VersionProfile::new(4));
+        let result = vm.load_binary("assets/a7/listrev.bin");
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error,
+            VmError::Load(LoadError::IllegalInstruction { .. })
+        ));
+        assert!(error.to_string().starts_with("Error: illegal instruction"));
+        vm.set_profile(VersionProfile::new(8));
+        vm.load_binary("assets/a4/prog02.bin").unwrap();
+    }
+
+    #[test]
+    fn test_load_bytes() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let bytes = std::fs::read("assets/a4/prog02.bin").unwrap();
+        vm.load_bytes(&bytes).unwrap();
+        vm.init();
+        vm.work().unwrap();
+        let output = String::from_utf8(stdout).unwrap();
+        let expected = std::fs::read_to_string("assets/a4/prog02.out").unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_load_bytes_checks_header() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let mut bytes = std::fs::read("assets/a4/prog02.bin").unwrap();
+        bytes[0] = b'X';
+        let result = vm.load_bytes(&bytes);
+        assert!(matches!(
+            result,
+            Err(VmError::Load(LoadError::InvalidBinary {
+                source: HeaderError::BadMagic { .. },
+                ..
+            }))
+        ));
+        bytes[0] = b'N';
+        let result = vm.load_bytes(&bytes[..bytes.len() - 1]);
+        assert!(matches!(
+            result,
+            Err(VmError::Load(LoadError::InvalidBinary {
+                source: HeaderError::Truncated { .. },
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_load_bytes_verifies() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let program = [
+            Instruction::encode_instruction(Pushc, 1).unwrap(),
+            Instruction::encode_instruction(Add, 0).unwrap(),
+            Instruction::encode_instruction(Halt, 0).unwrap(),
+        ];
+        let mut bytes = Header::new(3, 0).to_bytes().to_vec();
+        program
+            .iter()
+            .for_each(|word| bytes.extend_from_slice(&word.to_le_bytes()));
+        vm.load_bytes(&bytes).unwrap();
+        vm.set_verify(true);
+        let result = vm.load_bytes(&bytes);
+        assert!(matches!(
+            result,
+            Err(VmError::Verify(VerifyError::StackUnderflow { .. }))
+        ));
+        vm.load("assets/a8/factor.bin").unwrap();
+    }
+
+    #[test]
+    fn test_load_bytes_checks_branch_targets() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let binary = |code: &[Bytecode]| {
+            let header = Header::new(code.len() as u32, 0);
+            let mut bytes = header.to_bytes().to_vec();
+            code.iter()
+                .for_each(|word| bytes.extend_from_slice(&word.to_le_bytes()));
+            bytes
+        };
+        let program = [
+            Instruction::encode_instruction(Call, 2).unwrap(),
+            Instruction::encode_instruction(Halt, 0).unwrap(),
+            Instruction::encode_instruction(Ret, 0).unwrap(),
+        ];
+        vm.load_bytes(&binary(&program)).unwrap();
+        for target in [-1, 3] {
+            let program = [
+                Instruction::encode_instruction(Pushc, 0).unwrap(),
+                Instruction::encode_instruction(Brf, target).unwrap(),
+                Instruction::encode_instruction(Halt, 0).unwrap(),
+            ];
+            let error = vm.load_bytes(&binary(&program)).unwrap_err();
+            assert!(matches!(
+                error,
+                VmError::Load(LoadError::InvalidTarget {
+                    opcode: Brf,
+                    address: 1,
+                    count: 3,
+                    ..
+                })
+            ));
+            assert_eq!(
+                error.to_string(),
+                format!("Error: brf {target} at address 1 jumps outside the program (3 instructions)")
+            );
+        }
+    }
+
+    #[test]
+    fn test_constant_pool() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let source = "\
+\t.vers\t9
+\tpushk\t10000000
+\tpushk\t123456789012345678901234567890
+\tmul
+\twrint
+\thalt
+";
+        let program = asm::assembler::assemble(source).unwrap();
+        vm.load_bytes(&program.to_bytes()).unwrap();
+        vm.init();
+        vm.work().unwrap();
+        let mut bytes = program.to_bytes();
+        bytes.truncate(Header::SIZE + program.code.len() * 4);
+        let error = vm.load_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Error: pushk 0 at address 0 refers to no constant (0 constants)"
+        );
+        drop(vm);
+        let output = String::from_utf8(stdout).unwrap();
+        assert!(output.contains("1234567890123456789012345678900000000"));
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let io = InputOutput::new(&stdin[..], &mut stdout, &mut stderr);
+        let file = std::fs::File::open("assets/a4/prog02.bin").unwrap();
+        let mut vm = NinjaVM::from_reader(io, file).unwrap();
+        vm.init();
+        vm.work().unwrap();
+        let output = String::from_utf8(stdout).unwrap();
+        let expected = std::fs::read_to_string("assets/a4/prog02.out").unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_run_for() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.load("assets/a4/prog02.bin").unwrap();
+        vm.init();
+        let mut slices = 0;
+        loop {
+            match vm.run_for(10) {
+                RunState::OutOfFuel => slices += 1,
+                RunState::Halted => break,
+                state => panic!("unexpected state {state:?}"),
+            }
+        }
+        assert!(slices > 1);
+        let output = String::from_utf8(stdout).unwrap();
+        let expected = std::fs::read_to_string("assets/a4/prog02.out").unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_run_for_waits_for_input() {
+        let stdin = std::io::Cursor::new(Vec::new());
+        let mut vm =
+            NinjaVM::new(InputOutput::new(stdin, Vec::new(), Vec::new()));
+        vm.load("assets/a3/prog1.bin").unwrap();
+        vm.init();
+        assert!(matches!(vm.run_for(100), RunState::WaitingForInput));
+        assert_eq!(vm.ir.pc, 0);
+        vm.io_mut().stdin().get_mut().extend_from_slice(b"12");
+        assert!(matches!(vm.run_for(100), RunState::WaitingForInput));
+        assert_eq!(vm.ir.pc, 0);
+        vm.io_mut().stdin().get_mut().extend_from_slice(b" 18");
+        assert!(matches!(vm.run_for(100), RunState::WaitingForInput));
+        assert_ne!(vm.ir.pc, 0);
+        vm.io_mut().stdin().get_mut().extend_from_slice(b"\n");
+        assert!(matches!(vm.run_for(1000), RunState::Halted));
+        let (_, stdout, _) = vm.into_io().into_parts();
+        assert!(stdout.ends_with(b"6\nNinja Virtual Machine stopped\n"));
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        let error = vm
+            .execute_binary_for("assets/a4/prog02.bin", 5)
+            .unwrap_err();
+        assert!(matches!(error, VmError::StepLimit { steps: 5 }));
+        assert_eq!(error.exit_code(), 124);
+        let program = [Instruction::encode_instruction(Pushc, 1).unwrap()];
+        let mut bytes = Header::new(1, 0).to_bytes().to_vec();
+        bytes.extend_from_slice(&program[0].to_le_bytes());
+        vm.load_bytes(&bytes).unwrap();
+        vm.init();
+        let state = vm.run_for(5);
+        let expected = Trap::PastEnd { pc: 1 };
+        assert!(
+            matches!(state, RunState::Trapped(VmError::Trap(trap)) if trap == expected)
+        );
+    }
+
+    #[test]
+    fn test_work_past_end() {
+        let program = asm::assembler::assemble("\tpushc\t1\n\twrint\n");
+        let empty = Header::new(0, 0).to_bytes().to_vec();
+        for (bytes, end) in [(program.unwrap().to_bytes(), 2), (empty, 0)] {
+            let stdin = b"";
+            let mut stdout = Vec::new();
+            let mut stderr = Vec::new();
+            let mut vm = NinjaVM::new(InputOutput::new(
+                &stdin[..],
+                &mut stdout,
+                &mut stderr,
+            ));
+            vm.load_bytes(&bytes).unwrap();
+            vm.init();
+            let error = vm.work().unwrap_err();
+            let expected = Trap::PastEnd { pc: end };
+            assert!(matches!(error, VmError::Trap(trap) if trap == expected));
+            assert_eq!(
+                error.to_string(),
+                format!("Error: execution ran past the last instruction to address {end}")
+            );
+        }
+    }
+
+    #[test]
+    fn test_vm_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<NinjaVM<&[u8], Vec<u8>, Vec<u8>>>();
+        assert_send::<VmError>();
+    }
+
+    #[test]
+    fn test_concurrent_vms() {
+        let binary = std::fs::read("assets/a4/prog02.bin").unwrap();
+        let run = |binary: &[u8]| {
+            let io = InputOutput::new(&b""[..], Vec::new(), Vec::new());
+            let mut vm = NinjaVM::from_reader(io, binary).unwrap();
+            vm.init();
+            vm.work().unwrap();
+            vm.into_io().into_parts().1
+        };
+        let expected = run(&binary);
+        let outputs: Vec<Vec<u8>> = std::thread::scope(|scope| {
+            let workers: Vec<_> =
+                (0..8).map(|_| scope.spawn(|| run(&binary))).collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        assert!(outputs.iter().all(|output| *output == expected));
+    }
+
+    #[test]
+    fn test_load_binary_from_stdin() {
+        let stdin = std::fs::read("assets/a4/prog02.bin").unwrap();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.load(STDIN_ARG).unwrap();
+        vm.init();
+        vm.work().unwrap();
+        let output = String::from_utf8(stdout).unwrap();
+        let expected = std::fs::read_to_string("assets/a4/prog02.out").unwrap();
+        assert_eq!(output, expected);
+    }
 }
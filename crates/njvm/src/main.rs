@@ -1,9 +1,14 @@
 fn main() {
     let args: Vec<String> = std::env::args().skip(1).collect();
 
-    njvm::NinjaVM::<
+    let result = njvm::NinjaVM::<
         std::io::StdinLock<'_>,
-        std::io::StdoutLock<'_>,
+        std::io::BufWriter<std::io::StdoutLock<'_>>,
         std::io::StderrLock<'_>,
     >::start(args);
+
+    if let Err(error) = result {
+        eprintln!("{error}");
+        std::process::exit(error.exit_code());
+    }
 }
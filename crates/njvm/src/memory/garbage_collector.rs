@@ -0,0 +1,154 @@
+use std::fmt::Debug;
+use std::io::{BufRead, Write};
+use std::ptr;
+
+use bigint::{BigObjRef, BIP};
+
+use crate::error::{Trap, VmError};
+use crate::memory::heap::ObjRef;
+use crate::memory::stack::StackSlot;
+use crate::NinjaVM;
+
+impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> NinjaVM<R, W, E> {
+    pub fn new_compound(&mut self, fields: usize) -> Result<ObjRef, VmError> {
+        if let Some(object) = self.heap.new_compound(fields) {
+            return Ok(object);
+        }
+        self.collect_garbage();
+        self.heap
+            .new_compound(fields)
+            .ok_or(Trap::HeapOverflow.into())
+    }
+
+    pub fn new_primitive(&mut self, bytes: usize) -> Result<ObjRef, VmError> {
+        if let Some(object) = self.heap.new_primitive(bytes) {
+            return Ok(object);
+        }
+        self.collect_garbage();
+        self.heap
+            .new_primitive(bytes)
+            .ok_or(Trap::HeapOverflow.into())
+    }
+
+    /// Runs a stop and copy collection over the heap. The roots are the
+    /// static data area, the return value register and the stack.
+    pub fn collect_garbage(&mut self) {
+        self.collect_garbage_with_registers(&mut BIP {
+            op1: ptr::null_mut(),
+            op2: ptr::null_mut(),
+            res: ptr::null_mut(),
+            rem: ptr::null_mut(),
+        });
+    }
+
+    /// Runs a collection during an operation of the big integer processor,
+    /// whose `registers` are roots as well.
+    pub fn collect_garbage_with_registers(&mut self, registers: &mut BIP) {
+        let mut objects =
+            [registers.op1, registers.op2, registers.res, registers.rem]
+                .map(|register| self.heap.object_at(register as *const u8));
+        self.heap.flip();
+        let roots = self
+            .sda
+            .data
+            .iter_mut()
+            .chain(self.rv.iter_mut())
+            .chain(self.stack.data.iter_mut());
+        for slot in roots {
+            if let StackSlot::Object(object) = slot {
+                *object = self.heap.relocate(*object);
+            }
+        }
+        for object in objects.iter_mut() {
+            *object = self.heap.relocate(*object);
+        }
+        self.heap.scan();
+        if let Some(statistics) = self.heap.finish() {
+            self.io.write_stdout(&statistics);
+        }
+        let [op1, op2, res, rem] =
+            objects.map(|object| self.big_pointer(object));
+        *registers = BIP { op1, op2, res, rem };
+    }
+
+    fn big_pointer(&mut self, object: ObjRef) -> BigObjRef {
+        match self.heap.primitive_pointer(object) {
+            Ok(pointer) => pointer as BigObjRef,
+            Err(_) => ptr::null_mut(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::InputOutput;
+    use crate::memory::heap::Heap;
+
+    #[test]
+    fn test_collect_garbage_reclaims_unreachable_objects() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.heap = Heap::new(1);
+        vm.sda.data.resize(1, StackSlot::default());
+        vm.new_object(2).unwrap();
+        vm.dup().unwrap();
+        vm.pushc(42).unwrap();
+        vm.putf(0).unwrap();
+        vm.popg(0).unwrap();
+        for _ in 0..100 {
+            vm.new_object(10).unwrap();
+            vm.drop(1).unwrap();
+        }
+        assert!(vm.heap.used < vm.heap.half());
+        vm.pushg(0).unwrap();
+        vm.getf(0).unwrap();
+        assert_eq!(vm.pop_integer().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_collect_garbage_fails_on_live_objects() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.heap = Heap::new(1);
+        let result = (0..100).try_for_each(|_| vm.new_object(10));
+        assert!(matches!(result, Err(VmError::Trap(Trap::HeapOverflow))));
+    }
+
+    #[test]
+    fn test_halt_collects_garbage_with_stats() {
+        let stdin = b"";
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut vm = NinjaVM::new(InputOutput::new(
+            &stdin[..],
+            &mut stdout,
+            &mut stderr,
+        ));
+        vm.heap.stats = true;
+        vm.new_object(2).unwrap();
+        vm.new_object(3).unwrap();
+        vm.drop(1).unwrap();
+        vm.halt().unwrap();
+        let output = String::from_utf8(stdout).unwrap();
+        let expected = r#"Garbage Collector:
+    2 objects (48 bytes) allocated since last collection
+    1 objects (20 bytes) copied during this collection
+    4194284 of 4194304 bytes free after this collection
+Ninja Virtual Machine stopped
+"#;
+        assert_eq!(output, expected);
+    }
+}
@@ -1,45 +1,101 @@
-use std::{
-    cell::RefCell,
-    fmt::Debug,
-    io::{BufRead, StderrLock, StdinLock, StdoutLock, Write},
-    rc::Rc,
-};
+use njvm_cli::{UsageError, MAX_HEAP_MEMORY};
 
-use crate::io::InputOutput;
+use crate::{
+    cpu::immediate::Immediate,
+    error::{Trap, VmError},
+    memory::stack::StackSlot,
+};
 
 pub const DEFAULT_HEAP_MEMORY: usize = 8192;
 pub const KIBI: usize = 1024;
+pub const HEADER_SIZE: usize = 4;
+pub const FIELD_SIZE: usize = 8;
+pub const ALIGNMENT: usize = 4;
+pub const COMPOUND_FLAG: u32 = 0x80000000;
+pub const BROKEN_HEART: u32 = 0x40000000;
+pub const FORWARD_POINTER: u32 = 0x3fffffff;
+const NUMBER_TAG: u32 = 0;
+const OBJECT_TAG: u32 = 1;
+
+/// Reference to an object on the heap, holding the offset of its header.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct ObjRef(u32);
+
+impl ObjRef {
+    pub const NIL: ObjRef = ObjRef(u32::MAX);
+
+    pub fn new(offset: usize) -> Self {
+        Self(offset as u32)
+    }
+
+    pub fn is_nil(&self) -> bool {
+        *self == Self::NIL
+    }
+
+    pub fn offset(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl std::fmt::Display for ObjRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_nil() {
+            write!(f, "nil")
+        } else {
+            write!(f, "0x{:08x}", self.0)
+        }
+    }
+}
 
 #[derive(Debug, Eq, PartialEq, Clone)]
-pub struct Heap<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> {
-    pub io: Rc<RefCell<InputOutput<R, W, E>>>,
-    pub size: usize,      // Number of allocations
-    pub memory: usize,    // Total heap memory size
-    pub bytes: usize,     // Bytes used
-    pub available: usize, // Free bytes remaining
-    pub used: usize,      // Bytes in use
-    pub data: Box<[u8]>,  // The heap memory
-    pub begin: usize,     // Start of memory
-    pub active: usize,    // Active half
-    pub passive: usize,   // Passive half
-    pub next: usize,      // Next free byte
+pub struct Heap {
+    pub size: usize,   // Number of allocations since last collection
+    pub memory: usize, // Total heap memory size
+    pub bytes: usize,  // Total heap memory in bytes
+    pub available: usize, // Free bytes remaining in active half
+    pub used: usize,   // Bytes in use in active half
+    pub data: Box<[u8]>, // The heap memory
+    pub begin: usize,  // Start of memory
+    pub active: usize, // Active half
+    pub passive: usize, // Passive half
+    pub next: usize,   // Next free byte
+    pub allocated: usize, // Bytes allocated since last collection
+    pub copied: usize, // Objects copied during last collection
+    pub stats: bool,   // Show statistics after each collection
+    pub purge: bool,   // Zero the passive half after each collection
 }
 
-impl Default for Heap<StdinLock<'_>, StdoutLock<'_>, StderrLock<'_>> {
+impl Default for Heap {
     fn default() -> Self {
-        let io = InputOutput::default();
-        Self::new(Rc::new(RefCell::new(io)), DEFAULT_HEAP_MEMORY)
+        Self::new(DEFAULT_HEAP_MEMORY)
     }
 }
 
-impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> Heap<R, W, E> {
-    pub fn new(io: Rc<RefCell<InputOutput<R, W, E>>>, memory: usize) -> Self {
+impl Heap {
+    /// Creates a heap of `memory` KiB.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the heap cannot be created, see [`Heap::try_new`].
+    pub fn new(memory: usize) -> Self {
+        Self::try_new(memory).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Creates a heap of `memory` KiB, which fails if `memory` exceeds
+    /// [`MAX_HEAP_MEMORY`] or cannot be allocated.
+    pub fn try_new(memory: usize) -> Result<Self, VmError> {
+        if memory > MAX_HEAP_MEMORY {
+            return Err(UsageError::IllegalValue("heap size").into());
+        }
         let bytes = memory * KIBI;
         let available = bytes / 2;
-        let data = vec![0u8; bytes].into_boxed_slice();
+        let mut data = Vec::new();
+        data.try_reserve_exact(bytes)
+            .or(Err(VmError::OutOfMemory { memory: "heap" }))?;
+        data.resize(bytes, 0u8);
+        let data = data.into_boxed_slice();
 
-        Self {
-            io,
+        Ok(Self {
             size: 0,
             used: 0,
             memory,
@@ -49,18 +105,28 @@ impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> Heap<R, W, E> {
             active: 0,
             passive: bytes / 2,
             next: 0,
+            allocated: 0,
+            copied: 0,
+            stats: false,
+            purge: false,
             data,
-        }
+        })
+    }
+
+    pub fn half(&self) -> usize {
+        self.bytes / 2
     }
 
     pub fn allocate(&mut self, size: usize) -> Option<usize> {
-        if self.next + size > self.memory {
+        if size > self.available {
             None
         } else {
             let start = self.next;
             self.next += size;
             self.available -= size;
             self.used += size;
+            self.allocated += size;
+            self.size += 1;
             Some(start)
         }
     }
@@ -70,16 +136,393 @@ impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug> Heap<R, W, E> {
         offset: usize,
         len: usize,
     ) -> Option<&mut [u8]> {
-        if offset + len > self.memory {
+        if offset + len > self.bytes {
             None
         } else {
             Some(&mut self.data[offset..offset + len])
         }
     }
 
+    pub fn new_compound(&mut self, fields: usize) -> Option<ObjRef> {
+        let object = self.new_object(fields * FIELD_SIZE)?;
+        self.write_u32(object.offset(), COMPOUND_FLAG | fields as u32);
+        for field in 0..fields {
+            let offset = object.offset() + HEADER_SIZE + field * FIELD_SIZE;
+            self.write_slot(offset, StackSlot::default());
+        }
+        Some(object)
+    }
+
+    pub fn new_primitive(&mut self, bytes: usize) -> Option<ObjRef> {
+        let object = self.new_object(bytes)?;
+        self.write_u32(object.offset(), bytes as u32);
+        Some(object)
+    }
+
+    pub fn is_compound(&self, object: ObjRef) -> Result<bool, VmError> {
+        Ok(self.header(object)? & COMPOUND_FLAG != 0)
+    }
+
+    pub fn size_of(&self, object: ObjRef) -> Result<usize, VmError> {
+        Ok((self.header(object)? & !COMPOUND_FLAG) as usize)
+    }
+
+    pub fn primitive_data(&self, object: ObjRef) -> Result<&[u8], VmError> {
+        let offset = self.data_offset(object)?;
+        let size = self.size_of(object)?;
+        Ok(&self.data[offset..offset + size])
+    }
+
+    pub fn primitive_data_mut(
+        &mut self,
+        object: ObjRef,
+    ) -> Result<&mut [u8], VmError> {
+        let offset = self.data_offset(object)?;
+        let size = self.size_of(object)?;
+        Ok(&mut self.data[offset..offset + size])
+    }
+
+    /// Address of the header of a primitive object, as expected by the big
+    /// integer library. It stays valid until the next collection.
+    pub fn primitive_pointer(
+        &mut self,
+        object: ObjRef,
+    ) -> Result<*mut u8, VmError> {
+        let offset = self.data_offset(object)? - HEADER_SIZE;
+        Ok(self.data[offset..].as_mut_ptr())
+    }
+
+    /// The object whose header is at `pointer`, or nil for a null pointer.
+    pub fn object_at(&self, pointer: *const u8) -> ObjRef {
+        if pointer.is_null() {
+            return ObjRef::NIL;
+        }
+        ObjRef::new(pointer as usize - self.data.as_ptr() as usize)
+    }
+
+    pub fn get_field(
+        &self,
+        object: ObjRef,
+        index: Immediate,
+    ) -> Result<StackSlot, VmError> {
+        let offset = self.field_offset(object, index)?;
+        Ok(self.read_slot(offset))
+    }
+
+    pub fn set_field(
+        &mut self,
+        object: ObjRef,
+        index: Immediate,
+        value: StackSlot,
+    ) -> Result<(), VmError> {
+        let offset = self.field_offset(object, index)?;
+        self.write_slot(offset, value);
+        Ok(())
+    }
+
+    /// Swaps the semi-spaces, making the empty half the target of copying.
+    pub fn flip(&mut self) {
+        std::mem::swap(&mut self.active, &mut self.passive);
+        self.next = self.active;
+        self.used = 0;
+        self.available = self.half();
+        self.copied = 0;
+    }
+
+    /// Copies `object` into the active half unless it has been copied
+    /// already and returns its new location.
+    pub fn relocate(&mut self, object: ObjRef) -> ObjRef {
+        if object.is_nil() {
+            return object;
+        }
+        let offset = object.offset();
+        let header = self.read_u32(offset);
+        if header & BROKEN_HEART != 0 {
+            return ObjRef::new((header & FORWARD_POINTER) as usize);
+        }
+        let size = Self::object_size(header);
+        let target = self.next;
+        self.data.copy_within(offset..offset + size, target);
+        self.next += size;
+        self.used += size;
+        self.available -= size;
+        self.copied += 1;
+        self.write_u32(offset, BROKEN_HEART | target as u32);
+        ObjRef::new(target)
+    }
+
+    /// Relocates the fields of all compound objects copied so far.
+    pub fn scan(&mut self) {
+        let mut offset = self.active;
+        while offset < self.next {
+            let header = self.read_u32(offset);
+            if header & COMPOUND_FLAG != 0 {
+                let fields = (header & !COMPOUND_FLAG) as usize;
+                for field in 0..fields {
+                    let field = offset + HEADER_SIZE + field * FIELD_SIZE;
+                    if let StackSlot::Object(object) = self.read_slot(field) {
+                        let object = self.relocate(object);
+                        self.write_slot(field, StackSlot::Object(object));
+                    }
+                }
+            }
+            offset += Self::object_size(header);
+        }
+    }
+
+    /// Completes a collection and purges the passive half if requested.
+    /// Returns the statistics of the collection if they are requested.
+    pub fn finish(&mut self) -> Option<String> {
+        let mut statistics = None;
+        if self.stats {
+            let objects = self.size;
+            let allocated = self.allocated;
+            let copied = self.copied;
+            let used = self.used;
+            let available = self.available;
+            let half = self.half();
+            let message = format!(
+                "Garbage Collector:\n    \
+                {objects} objects ({allocated} bytes) allocated since last collection\n    \
+                {copied} objects ({used} bytes) copied during this collection\n    \
+                {available} of {half} bytes free after this collection\n"
+            );
+            statistics = Some(message);
+        }
+        if self.purge {
+            let passive = self.passive;
+            let half = self.half();
+            self.data[passive..passive + half].fill(0);
+        }
+        self.size = 0;
+        self.allocated = 0;
+        statistics
+    }
+
+    fn new_object(&mut self, payload: usize) -> Option<ObjRef> {
+        let size = HEADER_SIZE + payload.next_multiple_of(ALIGNMENT);
+        self.allocate(size).map(ObjRef::new)
+    }
+
+    fn object_size(header: u32) -> usize {
+        let size = (header & !COMPOUND_FLAG) as usize;
+        if header & COMPOUND_FLAG != 0 {
+            HEADER_SIZE + size * FIELD_SIZE
+        } else {
+            HEADER_SIZE + size.next_multiple_of(ALIGNMENT)
+        }
+    }
+
+    fn header(&self, object: ObjRef) -> Result<u32, VmError> {
+        if object.is_nil() {
+            return Err(Trap::NilReference.into());
+        }
+        Ok(self.read_u32(object.offset()))
+    }
+
+    fn field_offset(
+        &self,
+        object: ObjRef,
+        index: Immediate,
+    ) -> Result<usize, VmError> {
+        if !self.is_compound(object)? {
+            return Err(Trap::NotCompound.into());
+        }
+        if index < 0 || index as usize >= self.size_of(object)? {
+            return Err(Trap::IndexOutOfBounds.into());
+        }
+        Ok(object.offset() + HEADER_SIZE + index as usize * FIELD_SIZE)
+    }
+
+    fn data_offset(&self, object: ObjRef) -> Result<usize, VmError> {
+        if self.is_compound(object)? {
+            return Err(Trap::NotPrimitive.into());
+        }
+        Ok(object.offset() + HEADER_SIZE)
+    }
+
+    fn read_u32(&self, offset: usize) -> u32 {
+        let bytes = &self.data[offset..offset + 4];
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    fn write_u32(&mut self, offset: usize, value: u32) {
+        self.data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn read_slot(&self, offset: usize) -> StackSlot {
+        let value = self.read_u32(offset + 4);
+        match self.read_u32(offset) {
+            NUMBER_TAG => StackSlot::Number(value as Immediate),
+            _ => StackSlot::Object(ObjRef(value)),
+        }
+    }
+
+    fn write_slot(&mut self, offset: usize, slot: StackSlot) {
+        let (tag, value) = match slot {
+            StackSlot::Number(number) => (NUMBER_TAG, number as u32),
+            StackSlot::Object(object) => (OBJECT_TAG, object.0),
+        };
+        self.write_u32(offset, tag);
+        self.write_u32(offset + 4, value);
+    }
+
     pub fn reset(&mut self) {
-        self.next = 0;
-        self.available = self.memory;
+        self.next = self.active;
+        self.available = self.half();
         self.used = 0;
+        self.size = 0;
+        self.allocated = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_heap(memory: usize) -> Heap {
+        Heap::new(memory)
+    }
+
+    #[test]
+    fn test_heap() {
+        let heap = Heap::default();
+        assert_eq!(heap.bytes, DEFAULT_HEAP_MEMORY * KIBI);
+        assert_eq!(heap.available, heap.half());
+        assert_eq!(heap.next, 0);
+        assert_eq!(heap.size, 0);
+    }
+
+    #[test]
+    fn test_heap_too_big() {
+        let heap = Heap::try_new(MAX_HEAP_MEMORY + 1).unwrap_err();
+        assert_eq!(heap.to_string(), "Error: illegal heap size");
+        assert_eq!(Heap::try_new(1).unwrap().bytes, KIBI);
+    }
+
+    #[test]
+    fn test_new_compound() {
+        let mut heap = Heap::default();
+        let object = heap.new_compound(3).unwrap();
+        assert!(heap.is_compound(object).unwrap());
+        assert_eq!(heap.size_of(object).unwrap(), 3);
+        assert_eq!(heap.next, HEADER_SIZE + 3 * FIELD_SIZE);
+        for field in 0..3 {
+            let value = heap.get_field(object, field).unwrap();
+            assert_eq!(value, StackSlot::Object(ObjRef::NIL));
+        }
+        heap.set_field(object, 2, StackSlot::Number(42)).unwrap();
+        assert_eq!(heap.get_field(object, 2).unwrap(), StackSlot::Number(42));
+    }
+
+    #[test]
+    fn test_new_primitive() {
+        let mut heap = Heap::default();
+        let object = heap.new_primitive(5).unwrap();
+        assert!(!heap.is_compound(object).unwrap());
+        assert_eq!(heap.size_of(object).unwrap(), 5);
+        assert_eq!(heap.next, HEADER_SIZE + 8);
+        let next = heap.new_primitive(0).unwrap();
+        assert_eq!(next.offset(), HEADER_SIZE + 8);
+    }
+
+    #[test]
+    fn test_allocate_exhausts_active_half() {
+        let mut heap = new_heap(1);
+        assert_eq!(heap.allocate(heap.half()), Some(0));
+        assert_eq!(heap.allocate(1), None);
+        assert_eq!(heap.available, 0);
+    }
+
+    #[test]
+    fn test_collect() {
+        let mut heap = new_heap(1);
+        heap.new_compound(4).unwrap();
+        let list = heap.new_compound(2).unwrap();
+        let tail = heap.new_compound(2).unwrap();
+        heap.set_field(list, 0, StackSlot::Number(7)).unwrap();
+        heap.set_field(list, 1, StackSlot::Object(tail)).unwrap();
+        heap.set_field(tail, 1, StackSlot::Object(list)).unwrap();
+        heap.flip();
+        let list = heap.relocate(list);
+        heap.scan();
+        heap.finish();
+        assert_eq!(heap.copied, 2);
+        assert_eq!(heap.active, heap.half());
+        assert_eq!(list.offset(), heap.active);
+        assert_eq!(heap.used, 2 * (HEADER_SIZE + 2 * FIELD_SIZE));
+        assert_eq!(heap.get_field(list, 0).unwrap(), StackSlot::Number(7));
+        let StackSlot::Object(tail) = heap.get_field(list, 1).unwrap() else {
+            panic!("field does not hold an object reference");
+        };
+        assert_eq!(heap.size_of(tail).unwrap(), 2);
+        assert_eq!(heap.get_field(tail, 1).unwrap(), StackSlot::Object(list));
+        assert_eq!(
+            heap.get_field(tail, 0).unwrap(),
+            StackSlot::Object(ObjRef::NIL)
+        );
+    }
+
+    #[test]
+    fn test_collect_stats_and_purge() {
+        let mut heap = new_heap(1);
+        heap.stats = true;
+        heap.purge = true;
+        let object = heap.new_compound(1).unwrap();
+        heap.new_primitive(3).unwrap();
+        heap.flip();
+        heap.relocate(object);
+        heap.scan();
+        let output = heap.finish().unwrap();
+        assert!(heap.data[..heap.half()].iter().all(|byte| *byte == 0));
+        let expected = r#"Garbage Collector:
+    2 objects (20 bytes) allocated since last collection
+    1 objects (12 bytes) copied during this collection
+    500 of 512 bytes free after this collection
+"#;
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_field_out_of_bounds() {
+        let mut heap = new_heap(1);
+        let object = heap.new_compound(2).unwrap();
+        let result = heap.get_field(object, 2);
+        assert!(matches!(result, Err(VmError::Trap(Trap::IndexOutOfBounds))));
+    }
+
+    #[test]
+    fn test_primitive_data() {
+        let mut heap = new_heap(1);
+        let object = heap.new_primitive(5).unwrap();
+        heap.primitive_data_mut(object)
+            .unwrap()
+            .copy_from_slice(b"ninja");
+        assert_eq!(heap.primitive_data(object).unwrap(), b"ninja");
+    }
+
+    #[test]
+    fn test_primitive_pointer() {
+        let mut heap = new_heap(1);
+        heap.new_compound(1).unwrap();
+        let object = heap.new_primitive(5).unwrap();
+        let pointer = heap.primitive_pointer(object).unwrap();
+        assert_eq!(heap.object_at(pointer), object);
+        assert_eq!(heap.object_at(std::ptr::null()), ObjRef::NIL);
+    }
+
+    #[test]
+    fn test_primitive_data_of_compound() {
+        let mut heap = new_heap(1);
+        let object = heap.new_compound(1).unwrap();
+        let result = heap.primitive_data(object);
+        assert!(matches!(result, Err(VmError::Trap(Trap::NotPrimitive))));
+    }
+
+    #[test]
+    fn test_nil_reference() {
+        let heap = new_heap(1);
+        let result = heap.size_of(ObjRef::NIL);
+        assert!(matches!(result, Err(VmError::Trap(Trap::NilReference))));
     }
 }
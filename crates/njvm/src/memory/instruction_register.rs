@@ -1,77 +1,72 @@
-use std::fmt::Debug;
-use std::io::{StderrLock, StdinLock, StdoutLock};
-use std::{
-    cell::RefCell,
-    io::{BufRead, Write},
-    rc::Rc,
-};
+use std::fmt::{self, Display, Formatter};
 
-use crate::{
-    cpu::{immediate::Immediate, instruction::Instruction, opcode::Opcode},
-    io::InputOutput,
+use crate::cpu::{
+    immediate::Immediate, instruction::Instruction, opcode::Opcode,
 };
+use crate::error::{ImmediateOutOfRange, Trap, VmError};
 
 pub type Bytecode = u32;
 pub type ProgramCounter = usize;
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-pub struct InstructionRegister<
-    R: BufRead + Debug,
-    W: Write + Debug,
-    E: Write + Debug,
-> {
-    pub io: Rc<RefCell<InputOutput<R, W, E>>>,
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct InstructionRegister {
     pub pc: ProgramCounter,
     pub data: Vec<Bytecode>,
+    /// The instructions of `data` decoded once, so that executing them
+    /// does not decode every step.
+    decoded: Vec<Instruction>,
 }
 
-impl Default
-    for InstructionRegister<StdinLock<'_>, StdoutLock<'_>, StderrLock<'_>>
-{
-    fn default() -> Self {
-        let io = InputOutput::default();
-        Self::new(Rc::new(RefCell::new(io)), 0, 0)
-    }
-}
-
-impl<R: BufRead + Debug, W: Write + Debug, E: Write + Debug>
-    InstructionRegister<R, W, E>
-{
-    pub fn new(
-        io: Rc<RefCell<InputOutput<R, W, E>>>,
-        size: usize,
-        value: Bytecode,
-    ) -> Self {
-        let mut data = vec![];
-        data.resize(size, value);
-        InstructionRegister { io, pc: 0, data }
+impl InstructionRegister {
+    pub fn new(size: usize, value: Bytecode) -> Self {
+        let mut register = InstructionRegister {
+            pc: 0,
+            data: vec![],
+            decoded: vec![],
+        };
+        register.resize_data(size, value);
+        register
     }
 
     pub fn resize_data(&mut self, new_size: usize, value: Bytecode) {
         self.data.resize(new_size, value);
+        self.decoded.resize(new_size, Instruction::from(value));
+    }
+
+    /// Returns the decoded instruction at `pc`, which traps if `pc` lies
+    /// past the last instruction.
+    #[inline]
+    pub fn instruction(
+        &self,
+        pc: ProgramCounter,
+    ) -> Result<Instruction, VmError> {
+        match self.decoded.get(pc) {
+            Some(&instruction) => Ok(instruction),
+            None => Err(Trap::PastEnd { pc }.into()),
+        }
     }
 
     pub fn register_instruction(
         &mut self,
         opcode: Opcode,
         immediate: Immediate,
-    ) {
-        let instruction = Instruction::encode_instruction(opcode, immediate);
+    ) -> Result<(), ImmediateOutOfRange> {
+        let instruction = Instruction::encode_instruction(opcode, immediate)?;
         self.data[self.pc] = instruction;
+        self.decoded[self.pc] = Instruction::new(opcode, immediate);
         self.pc += 1;
+        Ok(())
     }
+}
 
-    pub fn print(&mut self) {
-        for pc in 0..self.data.len() {
-            self.print_instruction(pc);
+/// Lists every instruction with its address, one per line.
+impl Display for InstructionRegister {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (pc, &bytecode) in self.data.iter().enumerate() {
+            let instruction = Instruction::from(bytecode);
+            write!(f, "{pc:04}: {instruction}")?;
         }
-    }
-
-    pub fn print_instruction(&mut self, pc: usize) {
-        let bytecode = self.data[pc];
-        let instruction = Instruction::from(bytecode);
-        let instruction = format!("{pc:04}: {instruction}");
-        self.io.borrow().write_stdout(&instruction);
+        Ok(())
     }
 }
 
@@ -88,16 +83,20 @@ mod tests {
 
     #[test]
     fn test_data_instruction() {
-        let io = InputOutput::<StdinLock<'_>, StdoutLock<'_>, StderrLock<'_>>::default();
-        let mut instruction_cache =
-            InstructionRegister::new(Rc::new(RefCell::new(io)), 2, 0);
+        let mut instruction_cache = InstructionRegister::new(2, 0);
         instruction_cache
-            .register_instruction(crate::cpu::opcode::Opcode::Pushc, 1);
+            .register_instruction(crate::cpu::opcode::Opcode::Pushc, 1)
+            .unwrap();
         assert_eq!(instruction_cache.pc, 1);
         assert_eq!(instruction_cache.data[0], 0x01000001);
         instruction_cache
-            .register_instruction(crate::cpu::opcode::Opcode::Pushc, 2);
+            .register_instruction(crate::cpu::opcode::Opcode::Pushc, 2)
+            .unwrap();
         assert_eq!(instruction_cache.pc, 2);
         assert_eq!(instruction_cache.data[1], 0x01000002);
+        let instruction = instruction_cache.instruction(1).unwrap();
+        assert_eq!(instruction, Instruction::new(Opcode::Pushc, 2));
+        let error = instruction_cache.instruction(2).unwrap_err();
+        assert!(matches!(error, VmError::Trap(Trap::PastEnd { pc: 2 })));
     }
 }
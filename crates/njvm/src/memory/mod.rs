@@ -1,3 +1,4 @@
+pub mod garbage_collector;
 pub mod heap;
 pub mod instruction_register;
 pub mod stack;
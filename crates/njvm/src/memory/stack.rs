@@ -1,122 +1,224 @@
-use std::{
-    cell::RefCell,
-    fmt::{Debug, Display, Formatter, Result},
-    io::{BufRead, StderrLock, StdinLock, StdoutLock, Write},
-    rc::Rc,
+use std::fmt::{self, Debug, Display, Formatter};
+
+use crate::{
+    cpu::immediate::Immediate,
+    error::{Trap, VmError},
+    memory::heap::{ObjRef, KIBI},
 };
 
-use crate::{cpu::immediate::Immediate, io::InputOutput};
+pub const DEFAULT_STACK_MEMORY: usize = 64;
+/// The size of a slot in the reference implementation. The stack holds as
+/// many slots as the reference implementation for the same memory, so
+/// programs overflow it at the same depth.
+pub const SLOT_SIZE: usize = 16;
 
 pub type StackPointer = usize;
 pub type FramePointer = usize;
 
+/// A stack slot holds either a plain number, such as a saved frame pointer
+/// or a return address, or a reference to an object on the heap.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum StackSlot {
+    Number(Immediate),
+    Object(ObjRef),
+}
+
+impl Default for StackSlot {
+    fn default() -> Self {
+        StackSlot::Object(ObjRef::NIL)
+    }
+}
+
+impl Display for StackSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StackSlot::Number(number) => write!(f, "(number) {number}"),
+            StackSlot::Object(object) => write!(f, "(objref) {object}"),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
-pub struct Stack<
-    R: BufRead + Debug,
-    W: Write + Debug,
-    E: Write + Debug,
-    T: Clone + Debug + Display,
-> {
-    pub io: Rc<RefCell<InputOutput<R, W, E>>>,
+pub struct Stack<T: Clone + Debug + Display> {
     pub sp: StackPointer,
     pub fp: FramePointer,
     pub data: Vec<T>,
+    /// The number of slots that fit into the memory of the stack.
+    pub size: usize,
 }
 
-impl Default
-    for Stack<StdinLock<'_>, StdoutLock<'_>, StderrLock<'_>, Immediate>
-{
+impl Default for Stack<StackSlot> {
     fn default() -> Self {
-        let io = InputOutput::default();
-        Self::new(Rc::new(RefCell::new(io)))
+        Self::new(DEFAULT_STACK_MEMORY)
     }
 }
 
-impl<
-        R: BufRead + Debug,
-        W: Write + Debug,
-        E: Write + Debug,
-        T: Clone + Debug + Display,
-    > Stack<R, W, E, T>
-{
-    pub fn new(io: Rc<RefCell<InputOutput<R, W, E>>>) -> Self {
-        Stack {
-            io,
+impl<T: Clone + Debug + Display> Stack<T> {
+    /// Creates a stack with `memory` KiB of slots, which are allocated
+    /// up front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stack cannot be allocated, see [`Stack::try_new`].
+    pub fn new(memory: usize) -> Self {
+        Self::try_new(memory).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Creates a stack with `memory` KiB of slots, which fails if they
+    /// cannot be allocated.
+    pub fn try_new(memory: usize) -> Result<Self, VmError> {
+        let out_of_memory = VmError::OutOfMemory { memory: "stack" };
+        let Some(bytes) = memory.checked_mul(KIBI) else {
+            return Err(out_of_memory);
+        };
+        let size = bytes / SLOT_SIZE;
+        let mut data = Vec::new();
+        data.try_reserve_exact(size).or(Err(out_of_memory))?;
+        Ok(Stack {
             sp: 0,
             fp: 0,
-            data: vec![],
-        }
+            data,
+            size,
+        })
     }
 
-    pub fn push(&mut self, immediate: T) {
+    pub fn push(&mut self, immediate: T) -> Result<(), VmError> {
+        if self.sp >= self.size {
+            return Err(self.overflow());
+        }
         self.data.push(immediate);
         self.sp += 1;
+        Ok(())
     }
 
-    pub fn pop(&mut self) -> T {
-        if self.sp == 0 || self.data.is_empty() {
-            self.io
-                .borrow()
-                .fatal_error("Stack underflow: popped from empty stack\n");
+    /// Pops the top slot, which has to lie inside the current stack frame
+    /// or, without a frame, on the stack.
+    pub fn pop(&mut self) -> Result<T, VmError> {
+        if self.sp == 0 {
+            return Err(Trap::StackUnderflow.into());
+        }
+        if self.sp <= self.fp {
+            return Err(self.frame_underflow());
         }
-        self.sp -= 1;
         match self.data.pop() {
-            Some(immediate) => immediate,
-            None => self
-                .io
-                .borrow()
-                .fatal_error("Stack underflow: popped from empty stack\n"),
+            Some(immediate) => {
+                self.sp -= 1;
+                Ok(immediate)
+            }
+            None => Err(Trap::StackUnderflow.into()),
+        }
+    }
+
+    fn frame_underflow(&self) -> VmError {
+        Trap::FrameUnderflow {
+            sp: self.sp,
+            fp: self.fp,
         }
+        .into()
     }
 
-    pub fn print(&self) {
-        let output = format!("{self}");
-        self.io.borrow().write_stdout(&output);
+    fn overflow(&self) -> VmError {
+        Trap::StackOverflow {
+            sp: self.sp,
+            fp: self.fp,
+        }
+        .into()
     }
 }
 
-impl<
-        R: BufRead + Debug,
-        W: Write + Debug,
-        E: Write + Debug,
-        T: Clone + Debug + Display,
-    > Display for Stack<R, W, E, T>
-{
-    fn fmt(&self, _f: &mut Formatter<'_>) -> Result {
+impl Stack<StackSlot> {
+    pub fn push_number(&mut self, number: Immediate) -> Result<(), VmError> {
+        self.push(StackSlot::Number(number))
+    }
+
+    pub fn push_object(&mut self, object: ObjRef) -> Result<(), VmError> {
+        self.push(StackSlot::Object(object))
+    }
+
+    /// Pushes `slots` nil references, e.g. for the local variables of a
+    /// stack frame.
+    pub fn grow(&mut self, slots: usize) -> Result<(), VmError> {
+        if slots > self.size - self.sp {
+            return Err(self.overflow());
+        }
+        self.sp += slots;
+        self.data.resize(self.sp, StackSlot::default());
+        Ok(())
+    }
+
+    /// Releases the current stack frame and restores the frame pointer
+    /// that `asf` saved just below it.
+    pub fn pop_frame(&mut self) -> Result<(), VmError> {
+        let fp = self.fp;
+        if self.sp < fp {
+            return Err(self.frame_underflow());
+        }
+        let Some(saved) = fp.checked_sub(1) else {
+            return Err(Trap::StackUnderflow.into());
+        };
+        let StackSlot::Number(number) = self.data[saved] else {
+            return Err(Trap::NotANumber.into());
+        };
+        match usize::try_from(number) {
+            Ok(saved_fp) if saved_fp <= saved => {
+                self.data.truncate(saved);
+                self.sp = saved;
+                self.fp = saved_fp;
+                Ok(())
+            }
+            _ => Err(Trap::InvalidFramePointer {
+                fp: number,
+                sp: saved,
+            }
+            .into()),
+        }
+    }
+
+    pub fn pop_number(&mut self) -> Result<Immediate, VmError> {
+        match self.pop()? {
+            StackSlot::Number(number) => Ok(number),
+            StackSlot::Object(_) => Err(Trap::NotANumber.into()),
+        }
+    }
+
+    pub fn pop_object(&mut self) -> Result<ObjRef, VmError> {
+        match self.pop()? {
+            StackSlot::Object(object) => Ok(object),
+            StackSlot::Number(_) => Err(Trap::NotAnObject.into()),
+        }
+    }
+}
+
+impl<T: Clone + Debug + Display> Display for Stack<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let sp = self.sp;
         let fp = self.fp;
 
         for slot in (0..=self.data.len()).rev() {
             if sp == 0 && fp == 0 {
-                let message = format!("sp, fp ---> {slot:04}: xxxx");
-                self.io.borrow().write_stdout(&message);
+                write!(f, "sp, fp ---> {slot:04}: xxxx")?;
             } else if sp == fp {
                 let value = &self.data[slot];
-                let message = format!("sp, fp ---> {slot:04}: {value}\n");
-                self.io.borrow().write_stdout(&message);
+                writeln!(f, "sp, fp ---> {slot:04}: {value}")?;
             }
 
             if slot != sp && slot != fp {
                 let value = &self.data[slot];
-                let message = format!("{slot:04}: {value}\n");
-                self.io.borrow().write_stdout(&message);
+                writeln!(f, "{slot:04}: {value}")?;
             }
 
             if slot == sp && slot != fp {
-                let message = format!("sp ---> {sp:04}: xxxx\n");
-                self.io.borrow().write_stdout(&message);
+                writeln!(f, "sp ---> {sp:04}: xxxx")?;
             }
 
             if slot == fp && slot != sp && fp == 0 {
                 let value = &self.data[fp];
-                let message = format!("fp ---> {fp:04}: {value}");
-                self.io.borrow().write_stdout(&message);
+                write!(f, "fp ---> {fp:04}: {value}")?;
             }
 
             if slot == fp && slot != sp && fp != 0 {
                 let value = &self.data[fp];
-                let message = format!("fp ---> {fp:04}: {value}\n");
-                self.io.borrow().write_stdout(&message);
+                writeln!(f, "fp ---> {fp:04}: {value}")?;
             }
         }
 
@@ -135,39 +237,98 @@ mod tests {
         assert_eq!(stack.data.len(), 0);
     }
 
+    #[test]
+    fn test_stack_too_big() {
+        for memory in [usize::MAX / KIBI, usize::MAX / KIBI + 1] {
+            let error = Stack::<StackSlot>::try_new(memory).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "Error: cannot allocate stack memory"
+            );
+        }
+        assert_eq!(Stack::<StackSlot>::try_new(1).unwrap().size, 64);
+    }
+
     #[test]
     fn test_push() {
         let mut stack = Stack::default();
-        stack.push(1);
+        stack.push_number(1).unwrap();
         assert_eq!(stack.sp, 1);
-        assert_eq!(stack.data[0], 1);
-        stack.push(5);
+        assert_eq!(stack.data[0], StackSlot::Number(1));
+        stack.push_object(ObjRef::NIL).unwrap();
         assert_eq!(stack.sp, 2);
-        assert_eq!(stack.data[1], 5);
+        assert_eq!(stack.data[1], StackSlot::Object(ObjRef::NIL));
     }
 
     #[test]
     fn test_pop() {
         let mut stack = Stack::default();
-        stack.push(1);
+        stack.push_number(1).unwrap();
         assert_eq!(stack.sp, 1);
-        assert_eq!(stack.data[0], 1);
-        assert_eq!(stack.pop(), 1);
+        assert_eq!(stack.data[0], StackSlot::Number(1));
+        assert_eq!(stack.pop_number().unwrap(), 1);
         assert_eq!(stack.sp, 0);
         assert_eq!(stack.data.len(), 0);
+        stack.push_object(ObjRef::new(8)).unwrap();
+        assert_eq!(stack.pop_object().unwrap(), ObjRef::new(8));
+    }
+
+    #[test]
+    fn test_pop_number_fails_on_object() {
+        let mut stack = Stack::<StackSlot>::new(DEFAULT_STACK_MEMORY);
+        stack.push_object(ObjRef::NIL).unwrap();
+        let result = stack.pop_number();
+        assert!(matches!(result, Err(VmError::Trap(Trap::NotANumber))));
+    }
+
+    #[test]
+    fn test_stack_slot_display() {
+        assert_eq!(format!("{}", StackSlot::Number(-3)), "(number) -3");
+        let object = StackSlot::Object(ObjRef::new(16));
+        assert_eq!(format!("{object}"), "(objref) 0x00000010");
+        let nil = StackSlot::Object(ObjRef::NIL);
+        assert_eq!(format!("{nil}"), "(objref) nil");
     }
 
     #[test]
-    #[should_panic(expected = "Stack underflow: popped from empty stack")]
     fn test_stack_underflow() {
-        std::panic::set_hook(Box::new(|_| {}));
-        let stdin = b"";
-        let stdout = Vec::new();
-        let stderr = Vec::new();
-        let io = InputOutput::new(&stdin[..], stdout, stderr);
-        let mut stack = Stack::<&[u8], Vec<u8>, Vec<u8>, Immediate>::new(
-            Rc::new(RefCell::new(io)),
-        );
-        stack.pop();
+        let mut stack = Stack::<StackSlot>::new(DEFAULT_STACK_MEMORY);
+        let result = stack.pop();
+        assert!(matches!(result, Err(VmError::Trap(Trap::StackUnderflow))));
+    }
+
+    #[test]
+    fn test_frame_underflow() {
+        let mut stack = Stack::<StackSlot>::new(DEFAULT_STACK_MEMORY);
+        stack.push_number(7).unwrap();
+        stack.push_number(0).unwrap();
+        stack.fp = 2;
+        let result = stack.pop();
+        let expected = Trap::FrameUnderflow { sp: 2, fp: 2 };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
+        stack.pop_frame().unwrap();
+        assert_eq!((stack.sp, stack.fp), (1, 0));
+        assert_eq!(stack.pop_number().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_stack_overflow() {
+        let mut stack = Stack::<StackSlot>::new(1);
+        assert_eq!(stack.size, 64);
+        for number in 0..64 {
+            stack.push_number(number).unwrap();
+        }
+        let result = stack.push_number(64);
+        let expected = Trap::StackOverflow { sp: 64, fp: 0 };
+        assert!(matches!(result, Err(VmError::Trap(trap)) if trap == expected));
+        assert_eq!(stack.sp, 64);
+        let result = stack.grow(1);
+        assert!(matches!(
+            result,
+            Err(VmError::Trap(Trap::StackOverflow { .. }))
+        ));
+        stack.pop().unwrap();
+        stack.grow(1).unwrap();
+        assert_eq!(stack.data[63], StackSlot::default());
     }
 }
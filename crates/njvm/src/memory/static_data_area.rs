@@ -1,72 +1,34 @@
-use std::{
-    cell::RefCell,
-    fmt::{Debug, Display, Formatter, Result},
-    io::{BufRead, StderrLock, StdinLock, StdoutLock, Write},
-    rc::Rc,
-};
+use std::fmt::{Debug, Display, Formatter, Result};
 
-use crate::{cpu::immediate::Immediate, io::InputOutput};
+use crate::memory::stack::StackSlot;
 
 #[derive(Eq, PartialEq, Clone)]
-pub struct StaticDataArea<
-    R: BufRead + Debug,
-    W: Write + Debug,
-    E: Write + Debug,
-    T,
-> {
-    pub io: Rc<RefCell<InputOutput<R, W, E>>>,
+pub struct StaticDataArea<T> {
     pub data: Vec<T>,
 }
 
-impl Default
-    for StaticDataArea<StdinLock<'_>, StdoutLock<'_>, StderrLock<'_>, Immediate>
-{
+impl Default for StaticDataArea<StackSlot> {
     fn default() -> Self {
-        let io = InputOutput::default();
-        Self::new(Rc::new(RefCell::new(io)), 0, 0)
+        Self::new(0, StackSlot::default())
     }
 }
 
-impl<
-        R: BufRead + Debug,
-        W: Write + Debug,
-        E: Write + Debug,
-        T: Clone + Debug + Display,
-    > StaticDataArea<R, W, E, T>
-{
-    pub fn new(
-        io: Rc<RefCell<InputOutput<R, W, E>>>,
-        size: usize,
-        value: T,
-    ) -> Self {
+impl<T: Clone + Debug + Display> StaticDataArea<T> {
+    pub fn new(size: usize, value: T) -> Self {
         let mut data = vec![];
         data.resize(size, value);
-        StaticDataArea { io, data }
-    }
-
-    pub fn print(&self) {
-        let output = format!("{self:#?}");
-        self.io.borrow().write_stdout(&output);
+        StaticDataArea { data }
     }
 }
 
-impl<
-        R: BufRead + Debug,
-        W: Write + Debug,
-        E: Write + Debug,
-        T: Debug + Display,
-    > Debug for StaticDataArea<R, W, E, T>
-{
-    fn fmt(&self, _f: &mut Formatter<'_>) -> Result {
+impl<T: Debug + Display> Debug for StaticDataArea<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         for data in 0..self.data.len() {
+            let value = &self.data[data];
             if data == (self.data.len() - 1) {
-                let value = &self.data[data];
-                let message = format!("data[{data:04}]:\t{value}");
-                self.io.borrow().write_stdout(&message);
+                write!(f, "data[{data:04}]:\t{value}")?;
             } else {
-                let value = &self.data[data];
-                let message = format!("data[{data:04}]:\t{value}\n");
-                self.io.borrow().write_stdout(&message);
+                writeln!(f, "data[{data:04}]:\t{value}")?;
             }
         }
         Ok(())